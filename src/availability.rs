@@ -0,0 +1,206 @@
+use crate::connection::NewsConnection;
+use crate::messages::head::HeadType;
+use crate::messages::stat::{StatRequest, StatResponse};
+use crate::{Error, Result};
+use futures_util::future::join_all;
+use std::collections::VecDeque;
+
+const DEFAULT_WINDOW: usize = 64;
+
+pub struct SegmentAvailability {
+    id: String,
+    present: Vec<bool>,
+    checked: Vec<bool>,
+}
+
+pub struct AvailabilityReport {
+    segments: Vec<SegmentAvailability>,
+    errors: Vec<(usize, Error)>,
+}
+
+// Checks which message-ids are present on one or more servers. Every server is given as a pool
+// of connections, the ids are spread across them and checked with pipelined STAT commands.
+pub struct AvailabilityChecker<'a> {
+    servers: Vec<Vec<&'a mut NewsConnection>>,
+    window: usize,
+}
+
+impl SegmentAvailability {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // Presence per server, in the order the servers were added to the checker
+    pub fn present(&self) -> &Vec<bool> {
+        &self.present
+    }
+
+    pub fn present_on(&self, server: usize) -> bool {
+        self.present.get(server).copied().unwrap_or(false)
+    }
+
+    // Whether the server answered for this id, false if its connection failed before
+    pub fn checked_on(&self, server: usize) -> bool {
+        self.checked.get(server).copied().unwrap_or(false)
+    }
+
+    pub fn available(&self) -> bool {
+        self.present.iter().any(|p| *p)
+    }
+}
+
+impl AvailabilityReport {
+    pub fn segments(&self) -> &Vec<SegmentAvailability> {
+        &self.segments
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &SegmentAvailability> {
+        self.segments.iter().filter(|s| !s.available())
+    }
+
+    // All segments are available on at least one server
+    pub fn complete(&self) -> bool {
+        self.segments.iter().all(|s| s.available())
+    }
+
+    pub fn complete_on(&self, server: usize) -> bool {
+        self.segments.iter().all(|s| s.present_on(server))
+    }
+
+    // Connection failures by server index, ids of a failed connection are left unchecked
+    pub fn errors(&self) -> &Vec<(usize, Error)> {
+        &self.errors
+    }
+}
+
+impl<'a> AvailabilityChecker<'a> {
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_WINDOW)
+    }
+
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            servers: Vec::new(),
+            window: window.max(1),
+        }
+    }
+
+    pub fn server(mut self, connections: Vec<&'a mut NewsConnection>) -> Self {
+        self.servers.push(connections);
+        self
+    }
+
+    pub async fn check<I>(&mut self, ids: I) -> Result<AvailabilityReport>
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let ids = ids.into_iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        let window = self.window;
+
+        let mut segments = ids
+            .iter()
+            .map(|id| SegmentAvailability {
+                id: id.clone(),
+                present: vec![false; self.servers.len()],
+                checked: vec![false; self.servers.len()],
+            })
+            .collect::<Vec<_>>();
+
+        let mut checks = Vec::new();
+
+        for (server, connections) in self.servers.iter_mut().enumerate() {
+            let count = connections.len();
+
+            for (n, connection) in connections.iter_mut().enumerate() {
+                let share = ids
+                    .iter()
+                    .enumerate()
+                    .skip(n)
+                    .step_by(count)
+                    .collect::<Vec<_>>();
+
+                checks.push(async move {
+                    let mut found = Vec::new();
+                    let result = stat_pipelined(connection, share, window, &mut found).await;
+
+                    (server, found, result)
+                });
+            }
+        }
+
+        let mut errors = Vec::new();
+
+        // A failing connection does not discard the answers of the others
+        for (server, found, result) in join_all(checks).await {
+            for (index, present) in found {
+                segments[index].present[server] = present;
+                segments[index].checked[server] = true;
+            }
+
+            if let Err(e) = result {
+                errors.push((server, e));
+            }
+        }
+
+        Ok(AvailabilityReport { segments, errors })
+    }
+}
+
+impl Default for AvailabilityChecker<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// STAT responses carry no message-id when the article is missing, so they are matched to their
+// requests by order. Answers are collected into `found` as they arrive, so they survive a
+// failure. A connection that fails with replies outstanding is out of sync and marked broken.
+async fn stat_pipelined(
+    connection: &mut NewsConnection,
+    ids: Vec<(usize, &String)>,
+    window: usize,
+    found: &mut Vec<(usize, bool)>,
+) -> Result<()> {
+    let mut pending = VecDeque::with_capacity(window);
+    let result = stat_window(connection, ids, window, &mut pending, found).await;
+
+    if result.is_err() && !pending.is_empty() {
+        connection.set_broken();
+    }
+
+    result
+}
+
+async fn stat_window(
+    connection: &mut NewsConnection,
+    ids: Vec<(usize, &String)>,
+    window: usize,
+    pending: &mut VecDeque<usize>,
+    found: &mut Vec<(usize, bool)>,
+) -> Result<()> {
+    let mut ids = ids.into_iter();
+
+    loop {
+        while pending.len() < window {
+            let Some((index, id)) = ids.next() else {
+                break;
+            };
+
+            connection
+                .send(StatRequest::new(HeadType::MessageId(id.clone())))
+                .await?;
+            pending.push_back(index);
+        }
+
+        let Some(index) = pending.front().copied() else {
+            break;
+        };
+
+        let response = connection.read::<StatResponse>().await?;
+        pending.pop_front();
+        found.push((index, response.ok()));
+    }
+
+    Ok(())
+}
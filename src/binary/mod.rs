@@ -0,0 +1,123 @@
+use crate::binary::uuencode::UuFile;
+use crate::binary::yenc::YencPart;
+use crate::mime;
+use crate::Result;
+
+pub mod release;
+pub mod uuencode;
+pub mod yenc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    Yenc,
+    Uuencode,
+    Base64,
+}
+
+// A file found in an article body
+#[derive(Debug, Clone)]
+pub struct BinaryFile {
+    name: Option<String>,
+    encoding: BinaryEncoding,
+    data: Vec<u8>,
+}
+
+// Decodes the binaries of an article body, detecting yEnc, UUencode and base64 MIME
+// attachments in that order. Bodies without any of them yield no files. Every yEnc block is
+// decoded on its own, a broken one yields its error without affecting the other files.
+pub fn decode_binary(body: &[u8]) -> Vec<Result<BinaryFile>> {
+    // Every `=ybegin` line starts a file, decoding stops at its `=yend` line
+    let starts = lines(body)
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len() + 1;
+            Some((start, line))
+        })
+        .filter(|(_, line)| line.starts_with(b"=ybegin "))
+        .map(|(start, _)| start)
+        .collect::<Vec<_>>();
+
+    if !starts.is_empty() {
+        return starts
+            .into_iter()
+            .map(|start| {
+                let part = YencPart::decode(&body[start..])?;
+                let name = Some(part.name().to_string());
+
+                Ok(BinaryFile::new(
+                    name,
+                    BinaryEncoding::Yenc,
+                    part.into_data(),
+                ))
+            })
+            .collect();
+    }
+
+    let files = UuFile::decode(body);
+
+    if !files.is_empty() {
+        return files
+            .into_iter()
+            .map(|file| {
+                let name = Some(file.name().to_string());
+                Ok(BinaryFile::new(
+                    name,
+                    BinaryEncoding::Uuencode,
+                    file.into_data(),
+                ))
+            })
+            .collect();
+    }
+
+    // Without the article headers the boundary is taken from the first delimiter line
+    let Some(boundary) = lines(body)
+        .filter_map(|line| line.strip_prefix(b"--"))
+        .map(|boundary| boundary.trim_ascii_end())
+        .find(|boundary| !boundary.is_empty())
+        .map(|boundary| String::from_utf8_lossy(boundary).to_string())
+    else {
+        return Vec::new();
+    };
+
+    mime::parse_multipart(body, &boundary)
+        .into_iter()
+        .filter(|part| *part.transfer_encoding() == mime::TransferEncoding::Base64)
+        .map(|part| {
+            Ok(BinaryFile::new(
+                part.filename(),
+                BinaryEncoding::Base64,
+                part.into_data(),
+            ))
+        })
+        .collect()
+}
+
+fn lines(body: &[u8]) -> impl Iterator<Item = &[u8]> {
+    body.split(|b| *b == b'\n')
+}
+
+impl BinaryFile {
+    fn new(name: Option<String>, encoding: BinaryEncoding, data: Vec<u8>) -> Self {
+        Self {
+            name,
+            encoding,
+            data,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn encoding(&self) -> BinaryEncoding {
+        self.encoding
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
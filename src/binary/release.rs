@@ -0,0 +1,480 @@
+use crate::messages::xover::XoverMessage;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+// Binary conventions found in a subject like `My.Release [01/15] - "file.rar" yEnc (12/50)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinarySubject {
+    release: String,
+    filename: Option<String>,
+    file: Option<(usize, usize)>,
+    part: usize,
+    total: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    number: usize,
+    id: String,
+    bytes: usize,
+}
+
+// A file posted in one or more segments
+#[derive(Debug, Clone)]
+pub struct ReleaseFile {
+    name: String,
+    subject: String,
+    poster: String,
+    date: DateTime<Utc>,
+    index: Option<usize>,
+    total: usize,
+    groups: Vec<String>,
+    segments: BTreeMap<usize, Segment>,
+}
+
+// Files of one poster sharing a release name
+#[derive(Debug, Clone)]
+pub struct Release {
+    name: String,
+    poster: String,
+    total_files: Option<usize>,
+    files: Vec<ReleaseFile>,
+}
+
+// A `(12/50)`, `[01/15]` or `(12 of 50)` counter and its byte range in the subject
+struct Counter {
+    start: usize,
+    end: usize,
+    square: bool,
+    index: usize,
+    total: usize,
+}
+
+impl BinarySubject {
+    // Subjects without a part counter are not considered binary posts
+    pub fn parse(subject: &str) -> Option<Self> {
+        let counters = counters(subject);
+        let quoted = quoted(subject);
+
+        // The last round counter counts the segments, a square one only after the filename
+        let part = counters
+            .iter()
+            .rposition(|c| !c.square)
+            .or_else(|| {
+                let (_, end) = quoted?;
+                counters.iter().rposition(|c| c.start >= end)
+            })
+            .or_else(|| (counters.len() > 1).then(|| counters.len() - 1))?;
+
+        let segment = &counters[part];
+
+        if segment.total == 0 || segment.index > segment.total {
+            return None;
+        }
+
+        let (filename, start) = match quoted {
+            Some((start, end)) if end <= segment.start => {
+                (Some(&subject[start + 1..end - 1]), start)
+            }
+            _ => match unquoted(&subject[..segment.start]) {
+                Some((start, end)) => (Some(&subject[start..end]), start),
+                None => (None, segment.start),
+            },
+        };
+
+        let file = counters[..part]
+            .iter()
+            .rfind(|c| c.end <= start)
+            .or_else(|| counters[..part].first())
+            .filter(|c| c.total > 0 && c.index <= c.total);
+
+        // The release name is what precedes the filename, less the file counter and noise
+        let mut release = subject[..start].to_string();
+        if let Some(file) = file.filter(|c| c.end <= start) {
+            release.replace_range(file.start..file.end, "");
+        }
+
+        let release = release
+            .replace("yEnc", "")
+            .trim_matches(|c: char| c.is_whitespace() || "-_:\"[]()".contains(c))
+            .to_string();
+
+        let release = match (release.is_empty(), filename) {
+            (true, Some(filename)) => stem(filename).to_string(),
+            _ => release,
+        };
+
+        Some(Self {
+            release,
+            filename: filename.map(|f| f.to_string()),
+            file: file.map(|c| (c.index, c.total)),
+            part: segment.index,
+            total: segment.total,
+        })
+    }
+
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    // File number and file count of the release, like `[01/15]`
+    pub fn file(&self) -> Option<(usize, usize)> {
+        self.file
+    }
+
+    pub fn part(&self) -> usize {
+        self.part
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl Segment {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    // Message-id without angle brackets
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+impl ReleaseFile {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Subject of the first segment
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn poster(&self) -> &str {
+        &self.poster
+    }
+
+    pub fn date(&self) -> DateTime<Utc> {
+        self.date
+    }
+
+    // Number of the file within its release
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = &Segment> {
+        self.segments.values()
+    }
+
+    pub fn missing(&self) -> Vec<usize> {
+        (1..=self.total)
+            .filter(|number| !self.segments.contains_key(number))
+            .collect()
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.segments.values().map(|s| s.bytes).sum()
+    }
+
+    // Percentage of segments present
+    pub fn completion(&self) -> f64 {
+        self.segments.len() as f64 * 100.0 / self.total as f64
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.segments.len() == self.total
+    }
+}
+
+impl Release {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn poster(&self) -> &str {
+        &self.poster
+    }
+
+    // File count announced by the subjects
+    pub fn total_files(&self) -> Option<usize> {
+        self.total_files
+    }
+
+    pub fn files(&self) -> &[ReleaseFile] {
+        &self.files
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.files.iter().map(|f| f.bytes()).sum()
+    }
+
+    // Percentage of segments present, announced files that were not seen count as missing
+    pub fn completion(&self) -> f64 {
+        let files = self.files.len().max(self.total_files.unwrap_or_default());
+        self.files.iter().map(|f| f.completion()).sum::<f64>() / files as f64
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.files.len() >= self.total_files.unwrap_or_default()
+            && self.files.iter().all(|f| f.is_complete())
+    }
+
+    // NZB 1.1 document listing the segments of every file
+    pub fn nzb(&self) -> String {
+        let mut nzb = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            "<!DOCTYPE nzb PUBLIC \"-//newzBin//DTD NZB 1.1//EN\" ",
+            "\"http://www.newzbin.com/DTD/nzb/nzb-1.1.dtd\">\n",
+            "<nzb xmlns=\"http://www.newzbin.com/DTD/2003/nzb\">\n",
+        ));
+
+        for file in &self.files {
+            let _ = writeln!(
+                nzb,
+                " <file poster=\"{}\" date=\"{}\" subject=\"{}\">",
+                escape(&file.poster),
+                file.date.timestamp(),
+                escape(&file.subject)
+            );
+
+            nzb.push_str("  <groups>\n");
+            for group in &file.groups {
+                let _ = writeln!(nzb, "   <group>{}</group>", escape(group));
+            }
+            nzb.push_str("  </groups>\n  <segments>\n");
+
+            for segment in file.segments.values() {
+                let _ = writeln!(
+                    nzb,
+                    "   <segment bytes=\"{}\" number=\"{}\">{}</segment>",
+                    segment.bytes,
+                    segment.number,
+                    escape(&segment.id)
+                );
+            }
+            nzb.push_str("  </segments>\n </file>\n");
+        }
+
+        nzb.push_str("</nzb>\n");
+        nzb
+    }
+}
+
+// Groups overview entries into releases and their files. Entries without a part counter are
+// skipped, releases keep the order in which they were first seen.
+pub fn group_releases(messages: &[XoverMessage]) -> Vec<Release> {
+    let mut releases = Vec::<Release>::new();
+    let mut index = HashMap::<(String, String), usize>::new();
+
+    for message in messages {
+        let Some(subject) = BinarySubject::parse(message.subject()) else {
+            continue;
+        };
+
+        // Zero parts usually carry an NFO or description rather than a segment
+        if subject.part == 0 || message.id().is_empty() {
+            continue;
+        }
+
+        let key = (message.author().to_string(), subject.release.clone());
+        let release = *index.entry(key).or_insert_with(|| {
+            releases.push(Release {
+                name: subject.release.clone(),
+                poster: message.author().to_string(),
+                total_files: None,
+                files: Vec::new(),
+            });
+            releases.len() - 1
+        });
+
+        releases[release].add(message, subject);
+    }
+
+    for release in &mut releases {
+        release
+            .files
+            .sort_by(|a, b| (a.index, &a.name).cmp(&(b.index, &b.name)));
+    }
+
+    releases
+}
+
+impl Release {
+    fn add(&mut self, message: &XoverMessage, subject: BinarySubject) {
+        if let Some((_, total)) = subject.file {
+            self.total_files = self.total_files.max(Some(total));
+        }
+
+        let name = subject.filename.unwrap_or(subject.release);
+
+        let position = match self
+            .files
+            .iter()
+            .position(|f| f.name == name && f.total == subject.total)
+        {
+            Some(position) => position,
+            None => {
+                self.files.push(ReleaseFile {
+                    name,
+                    subject: message.subject().to_string(),
+                    poster: message.author().to_string(),
+                    date: message.date(),
+                    index: subject.file.map(|(index, _)| index),
+                    total: subject.total,
+                    groups: Vec::new(),
+                    segments: BTreeMap::new(),
+                });
+                self.files.len() - 1
+            }
+        };
+
+        let file = &mut self.files[position];
+
+        if subject.part == 1 {
+            file.subject = message.subject().to_string();
+        }
+        file.date = file.date.min(message.date());
+
+        if let Some(xref) = message.xref() {
+            for (group, _) in xref.entries() {
+                if !file.groups.contains(group) {
+                    file.groups.push(group.clone());
+                }
+            }
+        }
+
+        file.segments
+            .entry(subject.part)
+            .or_insert_with(|| Segment {
+                number: subject.part,
+                id: message.id().to_string(),
+                bytes: message.bytes().unwrap_or_default(),
+            });
+    }
+}
+
+fn counters(subject: &str) -> Vec<Counter> {
+    let mut counters = Vec::new();
+
+    for (start, open) in subject.match_indices(['(', '[']) {
+        let square = open == "[";
+        let close = if square { ']' } else { ')' };
+
+        let Some(length) = subject[start + 1..].find(close) else {
+            continue;
+        };
+
+        let inner = &subject[start + 1..start + 1 + length];
+        let Some((index, total)) = inner.split_once('/').or_else(|| inner.split_once(" of "))
+        else {
+            continue;
+        };
+
+        if let (Ok(index), Ok(total)) = (index.trim().parse(), total.trim().parse()) {
+            counters.push(Counter {
+                start,
+                end: start + length + 2,
+                square,
+                index,
+                total,
+            });
+        }
+    }
+
+    counters
+}
+
+// Byte range of the first quoted string, quotes included
+fn quoted(subject: &str) -> Option<(usize, usize)> {
+    let start = subject.find('"')?;
+    let length = subject[start + 1..].find('"')?;
+
+    (length > 0).then_some((start, start + length + 2))
+}
+
+// Byte range of the last word that looks like a filename
+fn unquoted(subject: &str) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    let mut found = None;
+
+    for word in subject.split(' ') {
+        let trimmed = word.trim_start_matches(|c| "-[(<:".contains(c));
+        let start = offset + word.len() - trimmed.len();
+        let trimmed = trimmed.trim_end_matches(|c| "-])>:,".contains(c));
+
+        if has_extension(trimmed) {
+            found = Some((start, start + trimmed.len()));
+        }
+
+        offset += word.len() + 1;
+    }
+
+    found
+}
+
+fn has_extension(name: &str) -> bool {
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+
+    !stem.is_empty()
+        && (1..=5).contains(&extension.len())
+        && extension.bytes().all(|b| b.is_ascii_alphanumeric())
+        && (extension.bytes().any(|b| b.is_ascii_alphabetic()) || extension.len() == 3)
+}
+
+// Filename without its extension and volume suffixes, `name.part01.rar` and
+// `name.vol03+04.par2` both become `name`
+fn stem(filename: &str) -> &str {
+    let mut stem = filename;
+
+    for _ in 0..2 {
+        let Some((rest, extension)) = stem.rsplit_once('.') else {
+            break;
+        };
+
+        let extension = extension.to_ascii_lowercase();
+        let volume = ["part", "vol"].iter().any(|prefix| {
+            extension
+                .strip_prefix(prefix)
+                .is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit() || b == b'+'))
+        });
+
+        let archive = ["rar", "7z", "zip", "tar", "par2"].contains(&extension.as_str());
+
+        if rest.is_empty() || (stem != filename && !volume && !archive) {
+            break;
+        }
+
+        stem = rest;
+    }
+
+    stem
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,198 @@
+use crate::mime::decode_base64;
+
+// A file of a UUencoded body. Bodies may hold several files, each between a `begin` and an
+// `end` line.
+#[derive(Debug, Clone)]
+pub struct UuFile {
+    name: String,
+    mode: u32,
+    data: Vec<u8>,
+}
+
+impl UuFile {
+    // Decodes every file of the body. Broken encoders are tolerated: stripped trailing spaces,
+    // wrong length characters, trailing checksum characters and a missing `end` line.
+    // `begin-base64` blocks are decoded as well.
+    pub fn decode(body: &[u8]) -> Vec<UuFile> {
+        let mut files = Vec::new();
+        let mut current: Option<(UuFile, bool)> = None;
+
+        for line in body.split(|b| *b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            let Some((file, base64)) = current.as_mut() else {
+                current = begin(line);
+                continue;
+            };
+
+            let end = match base64 {
+                true => line == b"====",
+                false => line.trim_ascii_end() == b"end",
+            };
+
+            // A new begin line closes a file without end
+            if end || begin(line).is_some() {
+                files.extend(current.take().map(|(file, _)| file));
+                current = begin(line).filter(|_| !end);
+                continue;
+            }
+
+            match base64 {
+                true => file.data.extend(decode_base64(line)),
+                false => decode_line(line, &mut file.data),
+            }
+        }
+
+        files.extend(current.map(|(file, _)| file));
+        files
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Unix permissions of the file
+    pub fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+// Parses `begin <mode> <name>` and `begin-base64 <mode> <name>`
+fn begin(line: &[u8]) -> Option<(UuFile, bool)> {
+    let line = String::from_utf8_lossy(line);
+
+    let (rest, base64) = match line.strip_prefix("begin-base64 ") {
+        Some(rest) => (rest, true),
+        None => (line.strip_prefix("begin ")?, false),
+    };
+
+    let (mode, name) = rest.trim_start().split_once(' ')?;
+    let mode = u32::from_str_radix(mode, 8).ok()?;
+    let name = name.trim();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let file = UuFile {
+        name: name.to_string(),
+        mode,
+        data: Vec::new(),
+    };
+
+    Some((file, base64))
+}
+
+fn decode_line(line: &[u8], data: &mut Vec<u8>) {
+    let Some((length, encoded)) = line.split_first() else {
+        return;
+    };
+
+    // A short line lost its trailing spaces, the missing characters are zero. Only a line with
+    // more characters than a checksum character accounts for has a wrong length character, the
+    // length is then taken from the characters, which only know it in whole groups of three.
+    let mut length = sixbit(*length) as usize;
+
+    if encoded.len() > length.div_ceil(3) * 4 + 1 {
+        length = encoded.len() / 4 * 3;
+    }
+
+    let mut decoded = Vec::with_capacity(length + 2);
+
+    for group in 0..length.div_ceil(3) {
+        // Missing characters were stripped trailing spaces, i.e. zero
+        let c = |i: usize| encoded.get(group * 4 + i).copied().map(sixbit).unwrap_or(0);
+
+        decoded.extend([
+            (c(0) << 2) | (c(1) >> 4),
+            (c(1) << 4) | (c(2) >> 2),
+            (c(2) << 6) | c(3),
+        ]);
+    }
+
+    decoded.truncate(length);
+    data.extend_from_slice(&decoded);
+}
+
+// Both space and backtick encode zero
+fn sixbit(c: u8) -> u8 {
+    c.wrapping_sub(b' ') & 0x3f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_line(data: &[u8]) -> Vec<u8> {
+        let c = |bits: u8| match bits & 0x3f {
+            0 => b'`',
+            bits => bits + b' ',
+        };
+
+        let mut line = vec![c(data.len() as u8)];
+        for group in data.chunks(3) {
+            let b = |i: usize| group.get(i).copied().unwrap_or(0);
+            line.extend([
+                c(b(0) >> 2),
+                c((b(0) << 4) | (b(1) >> 4)),
+                c((b(1) << 2) | (b(2) >> 6)),
+                c(b(2)),
+            ]);
+        }
+        line
+    }
+
+    fn decode(line: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        decode_line(line, &mut data);
+        data
+    }
+
+    #[test]
+    fn stripped_trailing_spaces() {
+        let mut data = (1..=45).collect::<Vec<u8>>();
+        data[39..].fill(0);
+
+        // Encoders writing zero as a space lose the last eight characters to stripping
+        let line = encode_line(&data)
+            .into_iter()
+            .map(|c| if c == b'`' { b' ' } else { c })
+            .collect::<Vec<_>>();
+        let line = line.trim_ascii_end();
+        assert_eq!(line.len(), 61 - 8);
+
+        assert_eq!(decode(line), data);
+    }
+
+    #[test]
+    fn all_zero_line() {
+        assert_eq!(decode(&encode_line(&[0; 45])), [0; 45]);
+        assert_eq!(decode(b"M"), [0; 45]);
+    }
+
+    #[test]
+    fn checksum_character() {
+        let data = b"a line with a checksum".to_vec();
+        let mut line = encode_line(&data);
+        line.push(b'Z');
+
+        assert_eq!(decode(&line), data);
+    }
+
+    #[test]
+    fn wrong_length_character() {
+        let data = (1..=45).collect::<Vec<u8>>();
+        let mut line = encode_line(&data);
+        line[0] = b'#';
+
+        assert_eq!(decode(&line), data);
+    }
+}
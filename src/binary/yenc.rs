@@ -0,0 +1,171 @@
+use crate::{Error, Result};
+
+// A single decoded yEnc part. Multi-part posts carry the position of the part within the file
+// as 1-based, inclusive `begin` and `end` offsets.
+#[derive(Debug, Clone, Default)]
+pub struct YencPart {
+    name: String,
+    size: usize,
+    line: usize,
+    part: Option<usize>,
+    total: Option<usize>,
+    begin: Option<usize>,
+    end: Option<usize>,
+    crc32: Option<u32>,
+    data: Vec<u8>,
+}
+
+impl YencPart {
+    // Decodes the first yEnc block found in `bytes`, verifying size and checksum if the
+    // trailer carries them
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut part = YencPart::default();
+        let mut lines = bytes
+            .split(|b| *b == b'\n')
+            .map(|l| l.strip_suffix(b"\r").unwrap_or(l));
+
+        let header = lines
+            .by_ref()
+            .find_map(|l| l.strip_prefix(b"=ybegin "))
+            .ok_or(Error::BinaryError("Missing yEnc header"))?;
+
+        part.parse_header(&String::from_utf8_lossy(header))?;
+
+        let mut trailer = None;
+
+        for line in lines {
+            if let Some(keywords) = line.strip_prefix(b"=ypart ") {
+                let keywords = String::from_utf8_lossy(keywords);
+                part.begin = keyword(&keywords, "begin")?;
+                part.end = keyword(&keywords, "end")?;
+            } else if let Some(keywords) = line.strip_prefix(b"=yend") {
+                trailer = Some(String::from_utf8_lossy(keywords).to_string());
+                break;
+            } else {
+                decode_line(line, &mut part.data);
+            }
+        }
+
+        let trailer = trailer.ok_or(Error::BinaryError("Missing yEnc trailer"))?;
+        part.verify(&trailer)?;
+
+        Ok(part)
+    }
+
+    fn parse_header(&mut self, header: &str) -> Result<()> {
+        // The name is the last keyword and may contain spaces
+        let (keywords, name) = header
+            .split_once("name=")
+            .ok_or(Error::BinaryError("Missing yEnc file name"))?;
+
+        self.name = name.trim().to_string();
+        self.size = keyword(keywords, "size")?.ok_or(Error::BinaryError("Missing yEnc size"))?;
+        self.line = keyword(keywords, "line")?.unwrap_or_default();
+        self.part = keyword(keywords, "part")?;
+        self.total = keyword(keywords, "total")?;
+
+        Ok(())
+    }
+
+    fn verify(&mut self, trailer: &str) -> Result<()> {
+        if let Some(size) = keyword::<usize>(trailer, "size")?
+            && size != self.data.len()
+        {
+            return Err(Error::BinaryError("yEnc size mismatch"));
+        }
+
+        // Parts are checked against their own checksum, single part posts against the file's
+        let expected = match self.part {
+            Some(_) => checksum(trailer, "pcrc32")?,
+            None => checksum(trailer, "crc32")?,
+        };
+
+        if let Some(expected) = expected
+            && expected != crc32fast::hash(&self.data)
+        {
+            return Err(Error::BinaryError("yEnc checksum mismatch"));
+        }
+
+        self.crc32 = checksum(trailer, "crc32")?;
+
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Size of the whole file
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn part(&self) -> Option<usize> {
+        self.part
+    }
+
+    pub fn total(&self) -> Option<usize> {
+        self.total
+    }
+
+    pub fn begin(&self) -> Option<usize> {
+        self.begin
+    }
+
+    pub fn end(&self) -> Option<usize> {
+        self.end
+    }
+
+    // Checksum of the whole file, if announced in the trailer
+    pub fn crc32(&self) -> Option<u32> {
+        self.crc32
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+fn decode_line(line: &[u8], data: &mut Vec<u8>) {
+    let mut bytes = line.iter();
+
+    while let Some(b) = bytes.next() {
+        let b = match b {
+            b'=' => match bytes.next() {
+                Some(b) => b.wrapping_sub(64),
+                None => break,
+            },
+            b => *b,
+        };
+
+        data.push(b.wrapping_sub(42));
+    }
+}
+
+// Value of a `key=value` keyword of a yEnc header or trailer line
+fn keyword<T: std::str::FromStr>(keywords: &str, key: &str) -> Result<Option<T>> {
+    keywords
+        .split_whitespace()
+        .find_map(|k| k.strip_prefix(key).and_then(|k| k.strip_prefix('=')))
+        .map(|v| {
+            v.parse()
+                .map_err(|_| Error::BinaryError("Invalid yEnc keyword"))
+        })
+        .transpose()
+}
+
+fn checksum(keywords: &str, key: &str) -> Result<Option<u32>> {
+    keyword::<String>(keywords, key)?
+        .map(|v| {
+            u32::from_str_radix(&v, 16).map_err(|_| Error::BinaryError("Invalid yEnc checksum"))
+        })
+        .transpose()
+}
@@ -1,166 +1,551 @@
-use crate::decoder::decoder::{Decode, Decoder, Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::{GreetingResponse, Response};
-use crate::server::NewsServer;
-use crate::{Error, Result};
-use bytes::{Buf, BytesMut};
-use std::fmt::Display;
-use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
-use tokio_rustls::client::TlsStream;
-use tokio_rustls::{rustls, TlsConnector};
-
-enum NewsConnectionKind {
-    Plaintext(TcpStream),
-    Tls(TlsStream<TcpStream>),
-}
-
-impl NewsConnectionKind {
-    fn plaintext(stream: TcpStream) -> Self {
-        NewsConnectionKind::Plaintext(stream)
-    }
-
-    fn tls(stream: TlsStream<TcpStream>) -> Self {
-        NewsConnectionKind::Tls(stream)
-    }
-
-    async fn read(&mut self, bytes: &mut BytesMut) -> Result<usize> {
-        let mut buffer = [0u8; 1024];
-
-        match match self {
-            NewsConnectionKind::Plaintext(s) => s.read(&mut buffer).await,
-            NewsConnectionKind::Tls(s) => s.read(&mut buffer).await,
-        } {
-            Ok(n) if n == 0 => return Err(Error::Eof),
-            Ok(n) => {
-                bytes.extend_from_slice(&buffer[..n]);
-                Ok(n)
-            }
-            Err(e) => Err(Error::ReadError(e)),
-        }
-    }
-
-    async fn write(&mut self, bytes: &mut BytesMut) -> Result<usize> {
-        match match self {
-            NewsConnectionKind::Plaintext(s) => s.write(&bytes[..]).await,
-            NewsConnectionKind::Tls(s) => s.write(&bytes[..]).await,
-        } {
-            Ok(n) => {
-                bytes.advance(n);
-                Ok(n)
-            }
-            Err(e) => Err(Error::WriteError(e)),
-        }
-    }
-}
-
-pub struct NewsConnection {
-    server: NewsServer,
-    inner: NewsConnectionKind,
-}
-
-impl NewsConnection {
-    pub async fn connect(server: NewsServer, tls: bool) -> Result<Self> {
-        let mut conn = match tls {
-            true => Self::connect_tls(server).await,
-            false => Self::connect_plaintext(server).await,
-        }?;
-
-        conn.read::<Response<GreetingResponse>>().await?;
-
-        Ok(conn)
-    }
-
-    async fn connect_plaintext(server: NewsServer) -> Result<Self> {
-        let stream = TcpStream::connect(server.addr())
-            .await
-            .map_err(Error::TcpError)?;
-
-        Ok(Self {
-            server,
-            inner: NewsConnectionKind::plaintext(stream),
-        })
-    }
-
-    async fn connect_tls(server: NewsServer) -> Result<Self> {
-        let NewsConnection {
-            server,
-            inner: NewsConnectionKind::Plaintext(stream),
-        } = Self::connect_plaintext(server).await?
-        else {
-            unreachable!()
-        };
-
-        let connector = tls_connector();
-        let sni = rustls_pki_types::ServerName::try_from(server.name().to_string())
-            .map_err(Error::InvalidSni)?;
-
-        Ok(Self {
-            server,
-            inner: NewsConnectionKind::tls(
-                connector
-                    .connect(sni, stream)
-                    .await
-                    .map_err(Error::TlsError)?,
-            ),
-        })
-    }
-
-    pub fn fqdn(&self) -> impl Display {
-        &self.server
-    }
-
-    pub async fn request<T>(&mut self, request: T) -> Result<Response<T::Response>>
-    where
-        T: Encode + ExpectedResponse,
-        T::Response: Default + Decode + ExpectedResponseCode,
-    {
-        self.request_explicit::<T::Response, T>(request).await
-    }
-
-    pub async fn request_explicit<R, T>(&mut self, request: T) -> Result<Response<R>>
-    where
-        T: Encode + ExpectedResponse,
-        R: Default + Decode + ExpectedResponseCode,
-    {
-        let mut buffer = BytesMut::new();
-        request.encode(&mut buffer)?;
-
-        self.write(buffer).await?;
-        self.read::<Response<R>>().await
-    }
-
-    async fn read<T>(&mut self) -> Result<T>
-    where
-        T: Default + Decode + ExpectedResponseCode,
-    {
-        let mut data = T::default();
-        let mut decoder = Decoder::new();
-
-        loop {
-            let _ = self.inner.read(&mut decoder).await?;
-
-            match data.decode(&mut decoder, 0) {
-                Ok(_) => return Ok(data),
-                Err(Error::DecodeNeedMoreBytes) => continue,
-                Err(e) => return Err(e),
-            }
-        }
-    }
-
-    async fn write(&mut self, mut data: BytesMut) -> Result<usize> {
-        self.inner.write(&mut data).await
-    }
-}
-
-fn tls_connector() -> TlsConnector {
-    let mut store = rustls::RootCertStore::empty();
-    store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-
-    let config = tokio_rustls::rustls::client::ClientConfig::builder()
-        .with_root_certificates(store)
-        .with_no_client_auth();
-
-    TlsConnector::from(Arc::new(config))
-}
+use crate::decoder::decoder::{Decode, Decoder, Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::auth::{AuthinfoSaslRequest, AuthinfoSaslResponse, SaslContinuation};
+use crate::messages::capabilities::{Capabilities, CapabilitiesRequest, CapabilitiesResponse};
+use crate::messages::date::DateRequest;
+use crate::messages::ihave::{IhaveRequest, IhaveResponse};
+use crate::messages::mode::{ModeReaderRequest, ModeReaderResponse};
+use crate::messages::quit::QuitRequest;
+use crate::messages::xover::{
+    RangeType, XfeatureRequest, XoverRequest, XoverResponse, XzverRequest,
+};
+use crate::messages::{GreetingResponse, Response};
+use crate::sasl::SaslMechanism;
+use crate::server::NewsServer;
+use crate::session::{ConnectionState, Mode, OverviewFormat, Session};
+use crate::{Error, Result};
+use bytes::{Buf, BytesMut};
+use chrono::{TimeDelta, Utc};
+use encoding_rs::Encoding;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::{rustls, TlsConnector};
+
+static NO_CAPABILITIES: Capabilities = Capabilities::EMPTY;
+
+// Time granted to the server to answer QUIT and to shut down the stream
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[allow(clippy::large_enum_variant)]
+enum NewsConnectionKind {
+    Plaintext(TcpStream),
+    Tls(TlsStream<TcpStream>),
+}
+
+impl NewsConnectionKind {
+    fn plaintext(stream: TcpStream) -> Self {
+        NewsConnectionKind::Plaintext(stream)
+    }
+
+    fn tls(stream: TlsStream<TcpStream>) -> Self {
+        NewsConnectionKind::Tls(stream)
+    }
+
+    async fn read(&mut self, bytes: &mut BytesMut) -> Result<usize> {
+        let mut buffer = [0u8; 1024];
+
+        match match self {
+            NewsConnectionKind::Plaintext(s) => s.read(&mut buffer).await,
+            NewsConnectionKind::Tls(s) => s.read(&mut buffer).await,
+        } {
+            Ok(0) => Err(Error::Eof),
+            Ok(n) => {
+                bytes.extend_from_slice(&buffer[..n]);
+                Ok(n)
+            }
+            Err(e) => Err(Error::ReadError(e)),
+        }
+    }
+
+    // Shuts the write side down, TLS streams send close_notify first
+    async fn shutdown(&mut self) -> Result<()> {
+        match self {
+            NewsConnectionKind::Plaintext(s) => s.shutdown().await,
+            NewsConnectionKind::Tls(s) => s.shutdown().await,
+        }
+        .map_err(Error::WriteError)
+    }
+
+    async fn write(&mut self, bytes: &mut BytesMut) -> Result<usize> {
+        match match self {
+            NewsConnectionKind::Plaintext(s) => s.write(&bytes[..]).await,
+            NewsConnectionKind::Tls(s) => s.write(&bytes[..]).await,
+        } {
+            Ok(n) => {
+                bytes.advance(n);
+                Ok(n)
+            }
+            Err(e) => Err(Error::WriteError(e)),
+        }
+    }
+}
+
+// Raw deflate streams of an active COMPRESS DEFLATE layer (RFC 8054)
+struct Deflate {
+    compress: Compress,
+    decompress: Decompress,
+}
+
+impl Deflate {
+    fn new() -> Self {
+        Self {
+            compress: Compress::new(Compression::default(), false),
+            decompress: Decompress::new(false),
+        }
+    }
+
+    // Every write is sync flushed, so that the peer can decompress the complete command
+    fn deflate(&mut self, mut input: &[u8]) -> Result<BytesMut> {
+        let mut output = Vec::with_capacity(input.len() + 64);
+
+        loop {
+            let before = self.compress.total_in();
+
+            self.compress
+                .compress_vec(input, &mut output, FlushCompress::Sync)
+                .map_err(Error::encode)?;
+
+            input = &input[(self.compress.total_in() - before) as usize..];
+
+            // Output space left means the flush is complete
+            if input.is_empty() && output.len() < output.capacity() {
+                return Ok(BytesMut::from(&output[..]));
+            }
+
+            output.reserve(output.capacity().max(64));
+        }
+    }
+
+    fn inflate(&mut self, mut input: &[u8], bytes: &mut BytesMut) -> Result<()> {
+        let mut output = Vec::with_capacity(input.len() * 4 + 64);
+
+        loop {
+            let before = self.decompress.total_in();
+            output.clear();
+
+            let status = self
+                .decompress
+                .decompress_vec(input, &mut output, FlushDecompress::Sync)
+                .map_err(Error::decode)?;
+
+            input = &input[(self.decompress.total_in() - before) as usize..];
+            bytes.extend_from_slice(&output);
+
+            if (input.is_empty() && output.len() < output.capacity()) || status == Status::StreamEnd
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ConnectionOptions {
+    capabilities: bool,
+    close_timeout: Option<Duration>,
+    fallback_charset: Option<&'static Encoding>,
+}
+
+pub struct NewsConnection {
+    server: NewsServer,
+    inner: NewsConnectionKind,
+    buffer: Decoder,
+    session: Session,
+    options: ConnectionOptions,
+    deflate: Option<Deflate>,
+}
+
+impl ConnectionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Fetch capabilities after connecting and whenever the server state changed, e.g. after
+    // authentication
+    pub fn capabilities(mut self, fetch: bool) -> Self {
+        self.capabilities = fetch;
+        self
+    }
+
+    // Charset for response text that is not valid UTF-8, e.g. `encoding_rs::WINDOWS_1252` for
+    // Latin-1 groups. Without it invalid sequences are replaced.
+    pub fn fallback_charset(mut self, charset: &'static Encoding) -> Self {
+        self.fallback_charset = Some(charset);
+        self
+    }
+
+    pub fn close_timeout(mut self, timeout: Duration) -> Self {
+        self.close_timeout = Some(timeout);
+        self
+    }
+}
+
+impl NewsConnection {
+    pub async fn connect(server: NewsServer, tls: bool) -> Result<Self> {
+        Self::connect_with(server, tls, ConnectionOptions::default()).await
+    }
+
+    pub async fn connect_with(
+        server: NewsServer,
+        tls: bool,
+        options: ConnectionOptions,
+    ) -> Result<Self> {
+        let mut conn = match tls {
+            true => Self::connect_tls(server).await,
+            false => Self::connect_plaintext(server).await,
+        }?;
+
+        conn.options = options;
+        conn.read::<GreetingResponse>().await?;
+
+        if conn.options.capabilities {
+            conn.refresh_capabilities().await?;
+        }
+
+        Ok(conn)
+    }
+
+    async fn connect_plaintext(server: NewsServer) -> Result<Self> {
+        let stream = TcpStream::connect(server.addr())
+            .await
+            .map_err(Error::TcpError)?;
+
+        Ok(Self {
+            server,
+            inner: NewsConnectionKind::plaintext(stream),
+            buffer: Decoder::new(),
+            session: Session::default(),
+            options: ConnectionOptions::default(),
+            deflate: None,
+        })
+    }
+
+    async fn connect_tls(server: NewsServer) -> Result<Self> {
+        let NewsConnection {
+            server,
+            inner: NewsConnectionKind::Plaintext(stream),
+            ..
+        } = Self::connect_plaintext(server).await?
+        else {
+            unreachable!()
+        };
+
+        let connector = tls_connector();
+        let sni = rustls_pki_types::ServerName::try_from(server.name().to_string())
+            .map_err(Error::InvalidSni)?;
+
+        Ok(Self {
+            server,
+            inner: NewsConnectionKind::tls(
+                connector
+                    .connect(sni, stream)
+                    .await
+                    .map_err(Error::TlsError)?,
+            ),
+            buffer: Decoder::new(),
+            session: Session::default(),
+            options: ConnectionOptions::default(),
+            deflate: None,
+        })
+    }
+
+    pub fn fqdn(&self) -> impl Display {
+        &self.server
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    // Capabilities as last fetched, empty if they were never fetched
+    pub fn capabilities(&self) -> &Capabilities {
+        self.session.capabilities().unwrap_or(&NO_CAPABILITIES)
+    }
+
+    pub async fn refresh_capabilities(&mut self) -> Result<&Capabilities> {
+        self.exchange::<CapabilitiesResponse, _>(CapabilitiesRequest::new())
+            .await?;
+
+        Ok(self.capabilities())
+    }
+
+    pub async fn request<T>(&mut self, request: T) -> Result<Response<T::Response>>
+    where
+        T: Encode + ExpectedResponse,
+        T::Response: Default + Decode + ExpectedResponseCode,
+    {
+        self.request_explicit::<T::Response, T>(request).await
+    }
+
+    pub async fn request_explicit<R, T>(&mut self, request: T) -> Result<Response<R>>
+    where
+        T: Encode + ExpectedResponse,
+        R: Default + Decode + ExpectedResponseCode,
+    {
+        // Transit servers reject reader commands until MODE READER was sent
+        if T::READER_COMMAND
+            && self.session.mode() != Mode::Reader
+            && self.capabilities().supports_mode_reader()
+            && !self.capabilities().supports_reader()
+        {
+            let mode = self
+                .exchange::<ModeReaderResponse, _>(ModeReaderRequest::new())
+                .await?;
+
+            // Without reader mode the command would fail, or be sent again with every request
+            if !mode.ok() {
+                return Err(Error::UnexpectedResponseCode(mode.code()));
+            }
+        }
+
+        let response = self.exchange::<R, T>(request).await?;
+
+        if self.options.capabilities && self.session.capabilities().is_none() {
+            self.refresh_capabilities().await?;
+        }
+
+        Ok(response)
+    }
+
+    async fn exchange<R, T>(&mut self, request: T) -> Result<Response<R>>
+    where
+        T: Encode,
+        R: Default + Decode + ExpectedResponseCode,
+    {
+        self.send(request).await?;
+        self.read::<R>().await
+    }
+
+    pub async fn ihave(&mut self, request: IhaveRequest) -> Result<Response<IhaveResponse>> {
+        let response = self.request(&request).await?;
+
+        // Peer does not want the article or asks us to retry later
+        if response.code() != 335 {
+            return Ok(response);
+        }
+
+        self.send(request.payload()).await?;
+        self.read::<IhaveResponse>().await
+    }
+
+    // Measures the offset of the server clock against the local clock with DATE, assuming the
+    // server answered halfway through the round trip. The skew is kept in the session and
+    // applied to the dates of NEWGROUPS and NEWNEWS.
+    pub async fn measure_clock_skew(&mut self) -> Result<TimeDelta> {
+        let sent = Utc::now();
+        let response = self.request(DateRequest::new()).await?;
+        let received = Utc::now();
+
+        // DATE truncates to seconds, on average the server time is half a second later
+        let server = response.datetime() + TimeDelta::milliseconds(500);
+        let skew = server - (sent + (received - sent) / 2);
+
+        self.session.set_clock_skew(skew);
+
+        Ok(skew)
+    }
+
+    // Fetches overview data using the first compressed extension the server supports: XZVER,
+    // then XFEATURE COMPRESS GZIP, and plain XOVER otherwise. The outcome is kept in the session.
+    pub async fn overview(&mut self, range: RangeType) -> Result<Response<XoverResponse>> {
+        if self.session.overview_format() == OverviewFormat::Unknown {
+            let response = self.request(XzverRequest::new(range)).await?;
+
+            if !matches!(response.code(), 500 | 501) {
+                self.session.set_overview_format(OverviewFormat::Xzver);
+                return Ok(response);
+            }
+
+            if !self.request(XfeatureRequest::compress_gzip()).await?.ok() {
+                self.session.set_overview_format(OverviewFormat::Plain);
+            }
+        }
+
+        match self.session.overview_format() {
+            OverviewFormat::Xzver => self.request(XzverRequest::new(range)).await,
+            _ => self.request(XoverRequest::new(range)).await,
+        }
+    }
+
+    // Sends QUIT and shuts the stream down, waiting a short time for each. Broken connections
+    // are shut down without QUIT. The connection is closed afterwards, even if QUIT failed.
+    pub async fn close(&mut self) -> Result<()> {
+        let timeout = self.options.close_timeout.unwrap_or(CLOSE_TIMEOUT);
+
+        let quit = match self.session.state() {
+            ConnectionState::Open | ConnectionState::Authenticated => {
+                match tokio::time::timeout(timeout, self.request(QuitRequest::new())).await {
+                    Ok(response) => response.map(|_| ()),
+                    Err(_) => Err(Error::Timeout),
+                }
+            }
+            ConnectionState::Broken | ConnectionState::Closed => Ok(()),
+        };
+
+        self.session.set_state(ConnectionState::Closed);
+
+        // The server does not care about a failed shutdown after QUIT
+        let _ = tokio::time::timeout(timeout, self.inner.shutdown()).await;
+
+        quit
+    }
+
+    // Runs AUTHINFO SASL, answering 383 challenges until the server accepts or rejects. A
+    // mechanism error cancels the exchange.
+    pub async fn authenticate_sasl<M>(
+        &mut self,
+        mechanism: &mut M,
+    ) -> Result<Response<AuthinfoSaslResponse>>
+    where
+        M: SaslMechanism,
+    {
+        let initial = mechanism.initial()?;
+        let mut response = self
+            .request(AuthinfoSaslRequest::new(mechanism.name(), initial))
+            .await?;
+
+        while response.code() == 383 {
+            let reply = match mechanism.step(response.data()) {
+                Ok(reply) => reply,
+                Err(e) => {
+                    self.request(SaslContinuation::cancel()).await?;
+                    return Err(e);
+                }
+            };
+
+            response = self.request(SaslContinuation::new(reply)).await?;
+        }
+
+        // A mechanism that authenticates the server must see its proof before the connection
+        // is trusted, a bare 281 does not carry one
+        if matches!(response.code(), 281 | 283) {
+            mechanism
+                .finish(response.data())
+                .inspect_err(|_| self.set_broken())?;
+        }
+
+        Ok(response)
+    }
+
+    pub(crate) async fn send<T>(&mut self, request: T) -> Result<usize>
+    where
+        T: Encode,
+    {
+        let mut buffer = BytesMut::new();
+        request.encode(&mut buffer, &self.session)?;
+
+        self.write(buffer).await
+    }
+
+    // Reads the next response. Bytes following it are kept for subsequent reads, so that
+    // pipelined responses are not lost.
+    pub(crate) async fn read<T>(&mut self) -> Result<Response<T>>
+    where
+        T: Default + Decode + ExpectedResponseCode,
+    {
+        self.usable()?;
+
+        let mut data = Response::<T>::default();
+
+        // Without a complete response the stream is out of sync
+        let len = self
+            .receive(&mut data)
+            .await
+            .inspect_err(|_| self.session.set_state(ConnectionState::Broken))?;
+
+        let mut decoder = Decoder::with_bytes(self.buffer.split_to(len))
+            .with_fallback(self.options.fallback_charset);
+
+        data.decode(&mut decoder, 0)?;
+        data.apply(&mut self.session, data.code());
+
+        // The server compresses everything following the COMPRESS response, including what was
+        // already read past it
+        if self.session.compressed() && self.deflate.is_none() {
+            let mut deflate = Deflate::new();
+            let raw = self.buffer.split();
+
+            deflate
+                .inflate(&raw, &mut self.buffer)
+                .inspect_err(|_| self.session.set_state(ConnectionState::Broken))?;
+
+            self.deflate = Some(deflate);
+        }
+
+        Ok(data)
+    }
+
+    // Reads until the buffer holds a complete response, returns its length
+    async fn receive<T>(&mut self, data: &mut T) -> Result<usize>
+    where
+        T: Decode,
+    {
+        loop {
+            if let Some(len) = data.frame(&self.buffer)? {
+                return Ok(len);
+            }
+
+            match &mut self.deflate {
+                Some(deflate) => {
+                    let mut raw = BytesMut::new();
+                    self.inner.read(&mut raw).await?;
+                    deflate.inflate(&raw, &mut self.buffer)?;
+                }
+                None => {
+                    self.inner.read(&mut self.buffer).await?;
+                }
+            }
+        }
+    }
+
+    async fn write(&mut self, data: BytesMut) -> Result<usize> {
+        self.usable()?;
+
+        self.transmit(data)
+            .await
+            .inspect_err(|_| self.session.set_state(ConnectionState::Broken))
+    }
+
+    async fn transmit(&mut self, mut data: BytesMut) -> Result<usize> {
+        let mut written = 0;
+
+        if let Some(deflate) = &mut self.deflate {
+            data = deflate.deflate(&data)?;
+        }
+
+        while !data.is_empty() {
+            written += self.inner.write(&mut data).await?;
+        }
+
+        Ok(written)
+    }
+
+    // Marks the connection as unusable, e.g. when pipelined responses were left unread
+    pub(crate) fn set_broken(&mut self) {
+        self.session.set_state(ConnectionState::Broken);
+    }
+
+    fn usable(&self) -> Result<()> {
+        match self.session.state() {
+            ConnectionState::Broken => Err(Error::ConnectionBroken),
+            ConnectionState::Closed => Err(Error::ConnectionClosed),
+            ConnectionState::Open | ConnectionState::Authenticated => Ok(()),
+        }
+    }
+}
+
+fn tls_connector() -> TlsConnector {
+    let mut store = rustls::RootCertStore::empty();
+    store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let config = tokio_rustls::rustls::client::ClientConfig::builder()
+        .with_root_certificates(store)
+        .with_no_client_auth();
+
+    TlsConnector::from(Arc::new(config))
+}
@@ -1,6 +1,9 @@
 use crate::decoder::utility::{Pipe, PositionWithLength};
+use crate::session::Session;
 use crate::Error;
 use bytes::{Buf, BufMut, BytesMut};
+use encoding_rs::Encoding;
+use std::borrow::Cow;
 use std::io::Write;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
@@ -25,6 +28,8 @@ const MULTI_LINE_TERMINATION: &[u8] = b"\r\n.\r\n";
 
 pub struct Decoder {
     bytes: BytesMut,
+    // Charset for text that is not valid UTF-8, decoded lossily as UTF-8 without one
+    fallback: Option<&'static Encoding>,
 }
 
 pub trait Decode: ExpectedResponseCode {
@@ -32,28 +37,7 @@ pub trait Decode: ExpectedResponseCode {
     where
         Self: Sized,
     {
-        // Check if response code is expected and the type of line termination
-        let term = if Self::CODES
-            .iter()
-            .find(|(c, _, _)| *c == code)
-            .ok_or_else(|| Error::UnexpectedResponseCode(code))?
-            .1
-        {
-            MULTI_LINE_TERMINATION
-        } else {
-            SINGLE_LINE_TERMINATION
-        };
-
-        // Check if - depending on line termination - we have enough bytes for the whole response
-        if bytes.len() < term.len() || &bytes[bytes.len() - term.len()..] != term {
-            return Err(Error::DecodeNeedMoreBytes);
-        }
-
-        // Remove line termination string if we have all we need
-        let len = bytes.len();
-        if code > 0 {
-            bytes.truncate(len - term.len());
-        }
+        bytes.terminate(Self::CODES, code)?;
 
         // Decode the message
         self.decoder(bytes, code)
@@ -62,16 +46,35 @@ pub trait Decode: ExpectedResponseCode {
     fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> crate::Result<()>
     where
         Self: Sized;
+
+    // Length of the first complete response in the buffer, `None` if more bytes are needed
+    fn frame(&mut self, bytes: &Decoder) -> crate::Result<Option<usize>>
+    where
+        Self: Sized,
+    {
+        bytes.frame(Self::CODES)
+    }
+
+    // Updates the session state of the connection after the response has been decoded
+    fn apply(&self, _session: &mut Session, _code: u16) {}
 }
 
 pub trait Encode {
-    fn encode(&self, bytes: &mut BytesMut) -> crate::Result<()> {
-        self.encoder(bytes)?;
+    // Command is only available in reader mode (RFC 3977 section 5.3)
+    const READER_COMMAND: bool = false;
+
+    fn encode(&self, bytes: &mut BytesMut, session: &Session) -> crate::Result<()> {
+        self.encoder_with(bytes, session)?;
 
         write!(bytes.writer(), "\r\n").map_err(Error::encode)
     }
 
     fn encoder(&self, bytes: &mut BytesMut) -> crate::Result<()>;
+
+    // Encodes the command adapted to the session state of the connection
+    fn encoder_with(&self, bytes: &mut BytesMut, _session: &Session) -> crate::Result<()> {
+        self.encoder(bytes)
+    }
 }
 
 pub trait ExpectedResponse {
@@ -102,13 +105,34 @@ impl DerefMut for Decoder {
 
 impl Decoder {
     pub(crate) fn new() -> Self {
+        Self::with_bytes(BytesMut::new())
+    }
+
+    pub(crate) fn with_bytes(bytes: BytesMut) -> Self {
         Self {
-            bytes: BytesMut::new(),
+            bytes,
+            fallback: None,
         }
     }
 
-    pub(crate) fn with_bytes(bytes: BytesMut) -> Self {
-        Self { bytes }
+    pub(crate) fn with_fallback(mut self, fallback: Option<&'static Encoding>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    // Decoder for other bytes of the same response, decoding text the same way
+    pub(crate) fn derive(&self, bytes: BytesMut) -> Decoder {
+        Decoder::with_bytes(bytes).with_fallback(self.fallback)
+    }
+
+    // Bytes as text. Invalid UTF-8 is decoded with the fallback charset or replaced, so that
+    // a single bad line does not fail the whole response.
+    pub(crate) fn text<'a>(&self, bytes: &'a [u8]) -> Cow<'a, str> {
+        match (str::from_utf8(bytes), self.fallback) {
+            (Ok(text), _) => Cow::Borrowed(text),
+            (Err(_), Some(fallback)) => fallback.decode_without_bom_handling(bytes).0,
+            (Err(_), None) => String::from_utf8_lossy(bytes),
+        }
     }
 
     pub(crate) fn as_slice(&self) -> &[u8] {
@@ -122,11 +146,91 @@ impl Decoder {
             .map(|p| PositionWithLength::new(p, need.len()))
     }
 
-    fn line_end(&self) -> Option<usize> {
+    pub(crate) fn line_end(&self) -> Option<usize> {
         self.position_multi(SINGLE_LINE_TERMINATION)
             .map(|p| p.position())
     }
 
+    // Length of the first complete response in the buffer. Responses with unknown codes are
+    // treated as single line, so that they are consumed and the stream stays in sync.
+    pub(crate) fn frame(&self, codes: ResponseCodeTuples) -> crate::Result<Option<usize>> {
+        let Some(end) = self.line_end() else {
+            return Ok(None);
+        };
+
+        let code = str::from_utf8(&self.bytes[..end.min(3)])
+            .map_err(Error::decode)?
+            .parse::<ResponseCode>()
+            .map_err(Error::decode)?;
+
+        let multi = codes
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .map(|(_, multi, _)| *multi)
+            .unwrap_or(false);
+
+        if !multi {
+            return Ok(Some(end + SINGLE_LINE_TERMINATION.len()));
+        }
+
+        Ok(self.bytes[end..]
+            .windows(MULTI_LINE_TERMINATION.len())
+            .position(|window| window == MULTI_LINE_TERMINATION)
+            .map(|p| end + p + MULTI_LINE_TERMINATION.len()))
+    }
+
+    // Checks that the response is complete and strips its termination
+    pub(crate) fn terminate(&mut self, codes: ResponseCodeTuples, code: u16) -> crate::Result<()> {
+        // Check if response code is expected and the type of line termination
+        let multi = codes
+            .iter()
+            .find(|(c, _, _)| *c == code)
+            .ok_or_else(|| Error::UnexpectedResponseCode(code))?
+            .1;
+
+        let term = match multi {
+            true => MULTI_LINE_TERMINATION,
+            false => SINGLE_LINE_TERMINATION,
+        };
+
+        // Check if - depending on line termination - we have enough bytes for the whole response
+        if self.len() < term.len() || &self[self.len() - term.len()..] != term {
+            return Err(Error::DecodeNeedMoreBytes);
+        }
+
+        // Remove line termination string if we have all we need
+        let len = self.len();
+        if code > 0 {
+            self.truncate(len - term.len());
+        }
+
+        if multi {
+            self.unstuff();
+        }
+
+        Ok(())
+    }
+
+    // Removes the dot stuffing of multi-line data blocks (RFC 3977 section 3.1.1)
+    fn unstuff(&mut self) {
+        const STUFFED: &[u8] = b"\r\n..";
+
+        if !self.bytes.windows(STUFFED.len()).any(|w| w == STUFFED) {
+            return;
+        }
+
+        let mut bytes = BytesMut::with_capacity(self.bytes.len());
+        let mut rest = &self.bytes[..];
+
+        while let Some(p) = rest.windows(STUFFED.len()).position(|w| w == STUFFED) {
+            bytes.extend_from_slice(&rest[..p + STUFFED.len() - 1]);
+            rest = &rest[p + STUFFED.len()..];
+        }
+
+        bytes.extend_from_slice(rest);
+        self.bytes = bytes;
+    }
+
     fn parse<T>(&mut self, to: usize) -> crate::Result<T>
     where
         T: FromStr,
@@ -135,8 +239,7 @@ impl Decoder {
         self.bytes
             .split_to(to)
             .as_ref()
-            .pipe(|b| str::from_utf8(&b[..]).map_err(Error::decode))?
-            .parse()
+            .pipe(|b| self.text(b).parse())
             .map_err(Error::decode)
     }
 
@@ -145,11 +248,7 @@ impl Decoder {
             return Ok(None);
         };
 
-        Ok(Some(
-            std::str::from_utf8(decoder.as_slice())
-                .map_err(Error::decode)?
-                .to_string(),
-        ))
+        Ok(Some(self.text(decoder.as_slice()).into_owned()))
     }
 
     pub(crate) fn line(&mut self) -> Option<Decoder> {
@@ -164,7 +263,7 @@ impl Decoder {
             self.bytes.advance(2);
         }
 
-        Some(Decoder::with_bytes(buffer))
+        Some(self.derive(buffer))
     }
 
     pub(crate) fn get_with_end<T>(&mut self, end: Option<PositionWithLength>) -> crate::Result<T>
@@ -222,9 +321,15 @@ impl<T> Encode for &T
 where
     T: Encode,
 {
+    const READER_COMMAND: bool = T::READER_COMMAND;
+
     fn encoder(&self, bytes: &mut BytesMut) -> crate::Result<()> {
         <T as Encode>::encoder(self, bytes)
     }
+
+    fn encoder_with(&self, bytes: &mut BytesMut, session: &Session) -> crate::Result<()> {
+        <T as Encode>::encoder_with(self, bytes, session)
+    }
 }
 
 impl<T> ExpectedResponse for &T
@@ -24,7 +24,7 @@ impl PartialEq for PositionWithLength {
 
 impl PartialOrd for PositionWithLength {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,141 @@
+use crate::connection::NewsConnection;
+use crate::messages::mode::ModeStreamRequest;
+use crate::messages::payload::ArticlePayload;
+use crate::messages::stream::{CheckRequest, StreamResponse, TakethisRequest};
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+const DEFAULT_WINDOW: usize = 32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeedStatus {
+    // Peer took the article (239)
+    Accepted,
+    // Peer already has the article or does not want it (438)
+    NotWanted,
+    // Peer asked to offer the article again later (431)
+    Deferred,
+    // Peer received the article but refused it (439)
+    Rejected,
+    // Peer asked for an article that was not offered (238 with an unknown message-id)
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct FeedResult {
+    id: String,
+    status: FeedStatus,
+}
+
+// Feeds articles to a peer in streaming mode (RFC 4644). Up to `window` CHECK and TAKETHIS
+// commands are kept outstanding, responses are matched to offers by message-id.
+pub struct StreamFeeder<'a> {
+    connection: &'a mut NewsConnection,
+    window: usize,
+    outstanding: usize,
+    offered: HashMap<String, ArticlePayload>,
+}
+
+impl FeedResult {
+    fn new(id: String, status: FeedStatus) -> Self {
+        Self { id, status }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn status(&self) -> FeedStatus {
+        self.status
+    }
+}
+
+impl<'a> StreamFeeder<'a> {
+    pub async fn new(connection: &'a mut NewsConnection) -> Result<Self> {
+        Self::with_window(connection, DEFAULT_WINDOW).await
+    }
+
+    pub async fn with_window(connection: &'a mut NewsConnection, window: usize) -> Result<Self> {
+        let response = connection.request(ModeStreamRequest::new()).await?;
+
+        if !response.ok() {
+            return Err(Error::UnexpectedResponseCode(response.code()));
+        }
+
+        Ok(Self {
+            connection,
+            window: window.max(1),
+            outstanding: 0,
+            offered: HashMap::new(),
+        })
+    }
+
+    // Offers an article with CHECK, it is sent with TAKETHIS once the peer wants it. Returns
+    // the results of all offers that completed in the meantime. A message-id can only be offered
+    // again once the peer answered its CHECK.
+    pub async fn offer(
+        &mut self,
+        id: impl ToString,
+        article: impl Into<ArticlePayload>,
+    ) -> Result<Vec<FeedResult>> {
+        let id = id.to_string();
+        let mut results = Vec::new();
+
+        if self.offered.contains_key(&id) {
+            return Err(Error::DuplicateOffer(id));
+        }
+
+        while self.outstanding >= self.window {
+            self.receive(&mut results).await?;
+        }
+
+        self.connection.send(CheckRequest::new(&id)).await?;
+        self.offered.insert(id, article.into());
+        self.outstanding += 1;
+
+        Ok(results)
+    }
+
+    // Waits for all outstanding offers and returns their results
+    pub async fn finish(mut self) -> Result<Vec<FeedResult>> {
+        let mut results = Vec::new();
+
+        while self.outstanding > 0 {
+            self.receive(&mut results).await?;
+        }
+
+        Ok(results)
+    }
+
+    async fn receive(&mut self, results: &mut Vec<FeedResult>) -> Result<()> {
+        let response = self.connection.read::<StreamResponse>().await?;
+        self.outstanding -= 1;
+
+        let id = response.id().to_string();
+
+        match response.code() {
+            238 => match self.offered.remove(&id) {
+                Some(payload) => {
+                    self.connection
+                        .send(TakethisRequest::new(&id, payload))
+                        .await?;
+                    self.outstanding += 1;
+                }
+                None => results.push(FeedResult::new(id, FeedStatus::Unknown)),
+            },
+            239 => results.push(FeedResult::new(id, FeedStatus::Accepted)),
+            431 => {
+                self.offered.remove(&id);
+                results.push(FeedResult::new(id, FeedStatus::Deferred));
+            }
+            438 => {
+                self.offered.remove(&id);
+                results.push(FeedResult::new(id, FeedStatus::NotWanted));
+            }
+            439 => results.push(FeedResult::new(id, FeedStatus::Rejected)),
+            code => return Err(Error::UnexpectedResponseCode(code)),
+        }
+
+        Ok(())
+    }
+}
@@ -2,10 +2,21 @@ use rustls_pki_types::InvalidDnsNameError;
 use std::fmt::{Debug, Display, Formatter};
 use std::num::ParseIntError;
 
+pub mod availability;
+pub mod binary;
 pub mod connection;
 pub mod decoder;
+pub mod feeder;
 pub mod messages;
+pub mod mime;
+pub mod newsrc;
+pub mod sasl;
 pub mod server;
+pub mod session;
+pub mod sync;
+pub mod threading;
+pub mod walker;
+pub mod wildmat;
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -24,6 +35,13 @@ pub enum Error {
     DecodeFromStr,
     DecodeError(Box<dyn std::error::Error>),
     EncodeError(Box<dyn std::error::Error>),
+    InvalidWildmat,
+    SaslError(&'static str),
+    BinaryError(&'static str),
+    ConnectionBroken,
+    ConnectionClosed,
+    Timeout,
+    DuplicateOffer(String),
 }
 
 impl Error {
@@ -45,19 +63,26 @@ impl Error {
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let e = match self {
-            Error::ServerNameNotFound => format!("Server must not be empty"),
+            Error::ServerNameNotFound => "Server must not be empty".to_string(),
             Error::ServerPortInvalid(e) => format!("Port is invalid {}", e),
             Error::InvalidSni(e) => format!("SNI is invalid {}", e),
             Error::TcpError(e) => format!("Unable to TCP connect {}", e),
             Error::TlsError(e) => format!("Unable to TLS handshake {}", e),
             Error::ReadError(e) => format!("Read failed with error {}", e),
             Error::WriteError(e) => format!("Write failed with error {}", e),
-            Error::Eof => format!("Stream closed"),
+            Error::Eof => "Stream closed".to_string(),
             Error::UnexpectedResponseCode(c) => format!("Unexpected response code {}", c),
-            Error::DecodeNeedMoreBytes => format!("Need more bytes"),
+            Error::DecodeNeedMoreBytes => "Need more bytes".to_string(),
             Error::DecodeError(e) => format!("Decode error {}", e),
-            Error::DecodeFromStr => format!("Attempt to decode from string failed"),
+            Error::DecodeFromStr => "Attempt to decode from string failed".to_string(),
             Error::EncodeError(e) => format!("Encode error {}", e),
+            Error::InvalidWildmat => "Wildmat is invalid".to_string(),
+            Error::SaslError(e) => format!("SASL authentication failed {}", e),
+            Error::BinaryError(e) => format!("Binary decoding failed {}", e),
+            Error::ConnectionBroken => "Connection is broken".to_string(),
+            Error::ConnectionClosed => "Connection is closed".to_string(),
+            Error::Timeout => "Timed out".to_string(),
+            Error::DuplicateOffer(id) => format!("Article {} is already offered", id),
         };
 
         write!(f, "{}", e)
@@ -1,77 +1,110 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::head::{HeadResponse, HeadType};
-use crate::messages::{Decode, Decoder, ResponseCodeTuples};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use std::collections::HashMap;
-use std::io::Write;
-
-pub struct ArticleRequest {
-    _type: HeadType,
-}
-
-#[derive(Default)]
-pub struct ArticleResponse {
-    header: HeadResponse,
-    body: Vec<u8>,
-}
-
-impl ArticleRequest {
-    pub fn new(_type: HeadType) -> Self {
-        Self { _type }
-    }
-}
-
-impl ArticleResponse {
-    pub fn number(&self) -> usize {
-        self.header.number()
-    }
-
-    pub fn id(&self) -> &str {
-        &self.header.id()
-    }
-
-    pub fn headers(&self) -> &HashMap<String, Vec<String>> {
-        &self.header.headers()
-    }
-
-    pub fn body(&self) -> &Vec<u8> {
-        &self.body
-    }
-}
-
-impl Encode for ArticleRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        let parameter = match &self._type {
-            HeadType::MessageId(i) => format!("<{}>", i.to_string()),
-            HeadType::MessageNumber(n) => n.to_string(),
-            HeadType::Empty => "".to_string(),
-        };
-
-        write!(bytes.writer(), "ARTICLE {}", parameter).map_err(Error::encode)
-    }
-}
-
-impl ExpectedResponse for ArticleRequest {
-    type Response = ArticleResponse;
-}
-
-impl ExpectedResponseCode for ArticleResponse {
-    const CODES: ResponseCodeTuples = &[(220, true, true), (430, false, false)];
-}
-
-impl Decode for ArticleResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        let mut header = HeadResponse::default();
-        header.decoder(bytes, code)?;
-
-        self.header = header;
-        self.body = bytes.as_slice().iter().cloned().collect();
-
-        Ok(())
-    }
-}
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::head::{HeadResponse, HeadType};
+use crate::messages::headers::Headers;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::mime::MimePart;
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub struct ArticleRequest {
+    _type: HeadType,
+}
+
+#[derive(Default)]
+pub struct ArticleResponse {
+    header: HeadResponse,
+    body: Vec<u8>,
+}
+
+impl ArticleRequest {
+    pub fn new(_type: HeadType) -> Self {
+        Self { _type }
+    }
+}
+
+impl ArticleResponse {
+    pub fn number(&self) -> usize {
+        self.header.number()
+    }
+
+    pub fn id(&self) -> &str {
+        self.header.id()
+    }
+
+    pub fn headers(&self) -> &Headers {
+        self.header.headers()
+    }
+
+    pub fn body(&self) -> &Vec<u8> {
+        &self.body
+    }
+
+    // Body split into MIME parts, a single part unless it is multipart
+    pub fn mime_parts(&self) -> Vec<MimePart> {
+        crate::mime::parse(self.headers(), &self.body)
+    }
+
+    pub fn attachments(&self) -> Vec<MimePart> {
+        self.mime_parts()
+            .into_iter()
+            .filter(|part| part.is_attachment())
+            .collect()
+    }
+}
+
+impl Encode for ArticleRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        let parameter = match &self._type {
+            HeadType::MessageId(i) => format!("<{}>", i),
+            HeadType::MessageNumber(n) => n.to_string(),
+            HeadType::Empty => "".to_string(),
+        };
+
+        write!(bytes.writer(), "ARTICLE {}", parameter).map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for ArticleRequest {
+    type Response = ArticleResponse;
+}
+
+impl ExpectedResponseCode for ArticleResponse {
+    const CODES: ResponseCodeTuples = &[
+        (220, true, true),
+        (412, false, false),
+        (420, false, false),
+        (423, false, false),
+        (430, false, false),
+    ];
+}
+
+impl Decode for ArticleResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if code != 220 {
+            return Ok(());
+        }
+
+        let mut header = HeadResponse::default();
+        header.decoder(bytes, code)?;
+
+        self.header = header;
+        self.body = bytes.as_slice().to_vec();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Selecting by message-id reports number 0 and keeps the current article
+        if code == 220 && self.number() > 0 {
+            session.select_article(self.number());
+        }
+    }
+}
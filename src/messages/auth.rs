@@ -1,67 +1,202 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::{Decode, Decoder, ResponseCodeTuples};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use std::io::Write;
-
-pub enum AuthinfoMode {
-    Username,
-    Password,
-}
-
-pub struct AuthinfoRequest {
-    mode: AuthinfoMode,
-    value: String,
-}
-
-#[derive(Default)]
-pub struct AuthinfoResponse {
-    text: String,
-}
-
-impl AuthinfoRequest {
-    pub fn new(mode: AuthinfoMode, value: impl ToString) -> Self {
-        Self {
-            mode,
-            value: value.to_string(),
-        }
-    }
-}
-
-impl AuthinfoResponse {
-    pub fn text(&self) -> &str {
-        &self.text
-    }
-}
-
-impl Encode for AuthinfoRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        let mode = match &self.mode {
-            AuthinfoMode::Username => "USER",
-            AuthinfoMode::Password => "PASS",
-        };
-
-        write!(bytes.writer(), "{} {} {}", "AUTHINFO", mode, self.value).map_err(Error::encode)
-    }
-}
-
-impl ExpectedResponse for AuthinfoRequest {
-    type Response = AuthinfoResponse;
-}
-
-impl ExpectedResponseCode for AuthinfoResponse {
-    const CODES: ResponseCodeTuples =
-        &[(281, false, true), (381, false, true), (502, false, false)];
-}
-
-impl Decode for AuthinfoResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        self.text = bytes.get_line()?.unwrap_or_default();
-
-        Ok(())
-    }
-}
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::{ConnectionState, Session};
+use crate::{Error, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub enum AuthinfoMode {
+    Username,
+    Password,
+}
+
+pub struct AuthinfoRequest {
+    mode: AuthinfoMode,
+    value: String,
+}
+
+#[derive(Default)]
+pub struct AuthinfoResponse {
+    text: String,
+}
+
+pub struct AuthinfoSaslRequest {
+    mechanism: String,
+    initial: Option<Vec<u8>>,
+}
+
+// Client response to a 383 SASL challenge, `None` cancels the exchange
+pub struct SaslContinuation {
+    data: Option<Vec<u8>>,
+}
+
+#[derive(Default)]
+pub struct AuthinfoSaslResponse {
+    text: String,
+    data: Vec<u8>,
+}
+
+impl AuthinfoRequest {
+    pub fn new(mode: AuthinfoMode, value: impl ToString) -> Self {
+        Self {
+            mode,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl AuthinfoResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl AuthinfoSaslRequest {
+    pub fn new(mechanism: impl ToString, initial: Option<Vec<u8>>) -> Self {
+        Self {
+            mechanism: mechanism.to_string(),
+            initial,
+        }
+    }
+}
+
+impl SaslContinuation {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data: Some(data) }
+    }
+
+    pub fn cancel() -> Self {
+        Self { data: None }
+    }
+}
+
+impl AuthinfoSaslResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // Decoded challenge (383) or additional success data (283)
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Encode for AuthinfoRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        let mode = match &self.mode {
+            AuthinfoMode::Username => "USER",
+            AuthinfoMode::Password => "PASS",
+        };
+
+        write!(bytes.writer(), "AUTHINFO {} {}", mode, self.value).map_err(Error::encode)
+    }
+}
+
+impl Encode for AuthinfoSaslRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "AUTHINFO SASL {}", self.mechanism).map_err(Error::encode)?;
+
+        if let Some(initial) = &self.initial {
+            write!(bytes.writer(), " {}", encode_sasl(initial)).map_err(Error::encode)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Encode for SaslContinuation {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        match &self.data {
+            Some(data) => write!(bytes.writer(), "{}", encode_sasl(data)),
+            None => write!(bytes.writer(), "*"),
+        }
+        .map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for AuthinfoRequest {
+    type Response = AuthinfoResponse;
+}
+
+impl ExpectedResponse for AuthinfoSaslRequest {
+    type Response = AuthinfoSaslResponse;
+}
+
+impl ExpectedResponse for SaslContinuation {
+    type Response = AuthinfoSaslResponse;
+}
+
+impl ExpectedResponseCode for AuthinfoResponse {
+    const CODES: ResponseCodeTuples = &[
+        (281, false, true),
+        (381, false, true),
+        (481, false, false),
+        (482, false, false),
+        (502, false, false),
+    ];
+}
+
+impl ExpectedResponseCode for AuthinfoSaslResponse {
+    const CODES: ResponseCodeTuples = &[
+        (281, false, true),
+        (283, false, true),
+        (383, false, true),
+        (481, false, false),
+        (482, false, false),
+        (502, false, false),
+    ];
+}
+
+impl Decode for AuthinfoSaslResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        // `=` stands for empty data
+        if (code == 283 || code == 383) && self.text.trim() != "=" {
+            self.data = STANDARD.decode(self.text.trim()).map_err(Error::decode)?;
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Capabilities change once authenticated (RFC 4643)
+        if code == 281 || code == 283 {
+            session.set_state(ConnectionState::Authenticated);
+            session.discard_capabilities();
+        }
+    }
+}
+
+impl Decode for AuthinfoResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Capabilities change once authenticated (RFC 4643)
+        if code == 281 {
+            session.set_state(ConnectionState::Authenticated);
+            session.discard_capabilities();
+        }
+    }
+}
+
+// Base64 for SASL exchanges, empty data is sent as `=`
+fn encode_sasl(data: &[u8]) -> String {
+    match data.is_empty() {
+        true => "=".to_string(),
+        false => STANDARD.encode(data),
+    }
+}
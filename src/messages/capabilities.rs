@@ -1,55 +1,207 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::{ExpectedResponseCode, ResponseCodeTuples};
-use crate::messages::{Decode, Decoder};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use std::io::Write;
-
-pub struct CapabilitiesRequest;
-
-#[derive(Default)]
-pub struct CapabilitiesResponse {
-    caps: Vec<String>,
-}
-
-impl CapabilitiesRequest {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
-impl CapabilitiesResponse {
-    pub fn text(&self) -> &Vec<String> {
-        &self.caps
-    }
-}
-
-impl Encode for CapabilitiesRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        write!(bytes.writer(), "CAPABILITIES").map_err(Error::encode)
-    }
-}
-
-impl ExpectedResponse for CapabilitiesRequest {
-    type Response = CapabilitiesResponse;
-}
-
-impl ExpectedResponseCode for CapabilitiesResponse {
-    const CODES: ResponseCodeTuples = &[(101, true, true)];
-}
-
-impl Decode for CapabilitiesResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        // Discard first line
-        let _ = bytes.line();
-
-        while let Ok(Some(line)) = bytes.get_line() {
-            self.caps.push(line);
-        }
-
-        Ok(())
-    }
-}
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::{ExpectedResponseCode, ResponseCodeTuples};
+use crate::messages::{Decode, Decoder};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+#[derive(Default)]
+pub struct CapabilitiesRequest;
+
+#[derive(Default)]
+pub struct CapabilitiesResponse {
+    caps: Vec<String>,
+}
+
+// Capabilities advertised by a server (RFC 3977 section 5.2), labels are kept upper case
+#[derive(Debug, Clone, Default)]
+pub struct Capabilities {
+    entries: Vec<(String, Vec<String>)>,
+}
+
+impl CapabilitiesRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CapabilitiesResponse {
+    pub fn text(&self) -> &Vec<String> {
+        &self.caps
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::parse(&self.caps)
+    }
+}
+
+impl Capabilities {
+    pub(crate) const EMPTY: Capabilities = Capabilities {
+        entries: Vec::new(),
+    };
+
+    fn parse(lines: &[String]) -> Self {
+        let entries = lines
+            .iter()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let label = parts.next()?.to_ascii_uppercase();
+
+                Some((label, parts.map(|p| p.to_string()).collect()))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Arguments of a capability, `None` if it is not advertised
+    pub fn get(&self, label: &str) -> Option<&Vec<String>> {
+        self.entries
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(label))
+            .map(|(_, args)| args)
+    }
+
+    pub fn has(&self, label: &str) -> bool {
+        self.get(label).is_some()
+    }
+
+    fn has_argument(&self, label: &str, argument: &str) -> bool {
+        self.get(label)
+            .is_some_and(|args| args.iter().any(|a| a.eq_ignore_ascii_case(argument)))
+    }
+
+    pub fn version(&self) -> Vec<usize> {
+        self.get("VERSION")
+            .map(|args| args.iter().filter_map(|v| v.parse().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn implementation(&self) -> Option<String> {
+        self.get("IMPLEMENTATION").map(|args| args.join(" "))
+    }
+
+    pub fn supports_reader(&self) -> bool {
+        self.has("READER")
+    }
+
+    pub fn supports_mode_reader(&self) -> bool {
+        self.has("MODE-READER")
+    }
+
+    pub fn supports_post(&self) -> bool {
+        self.has("POST")
+    }
+
+    pub fn supports_ihave(&self) -> bool {
+        self.has("IHAVE")
+    }
+
+    pub fn supports_streaming(&self) -> bool {
+        self.has("STREAMING")
+    }
+
+    pub fn supports_newnews(&self) -> bool {
+        self.has("NEWNEWS")
+    }
+
+    pub fn supports_over(&self) -> bool {
+        self.has("OVER")
+    }
+
+    pub fn supports_over_msgid(&self) -> bool {
+        self.has_argument("OVER", "MSGID")
+    }
+
+    pub fn supports_hdr(&self) -> bool {
+        self.has("HDR")
+    }
+
+    // LIST keywords, e.g. ACTIVE or OVERVIEW.FMT
+    pub fn list(&self) -> Vec<&str> {
+        self.arguments("LIST")
+    }
+
+    pub fn supports_list(&self, keyword: &str) -> bool {
+        self.has_argument("LIST", keyword)
+    }
+
+    // AUTHINFO variants, USER and/or SASL
+    pub fn authinfo(&self) -> Vec<&str> {
+        self.arguments("AUTHINFO")
+    }
+
+    pub fn supports_authinfo(&self, variant: &str) -> bool {
+        self.has_argument("AUTHINFO", variant)
+    }
+
+    pub fn sasl(&self) -> Vec<&str> {
+        self.arguments("SASL")
+    }
+
+    pub fn supports_sasl(&self, mechanism: &str) -> bool {
+        self.has_argument("SASL", mechanism)
+    }
+
+    pub fn supports_starttls(&self) -> bool {
+        self.has("STARTTLS")
+    }
+
+    pub fn compress(&self) -> Vec<&str> {
+        self.arguments("COMPRESS")
+    }
+
+    pub fn supports_compress(&self, algorithm: &str) -> bool {
+        self.has_argument("COMPRESS", algorithm)
+    }
+
+    fn arguments(&self, label: &str) -> Vec<&str> {
+        self.get(label)
+            .map(|args| args.iter().map(|a| a.as_str()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Encode for CapabilitiesRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "CAPABILITIES").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for CapabilitiesRequest {
+    type Response = CapabilitiesResponse;
+}
+
+impl ExpectedResponseCode for CapabilitiesResponse {
+    const CODES: ResponseCodeTuples = &[(101, true, true), (500, false, false)];
+}
+
+impl Decode for CapabilitiesResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if code != 101 {
+            return Ok(());
+        }
+
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            self.caps.push(line);
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, _: u16) {
+        // Servers without CAPABILITIES (500) are recorded as advertising nothing
+        session.set_capabilities(self.capabilities());
+    }
+}
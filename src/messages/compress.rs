@@ -0,0 +1,62 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+// RFC 8054 COMPRESS, DEFLATE is the only algorithm defined
+#[derive(Default)]
+pub struct CompressRequest;
+
+#[derive(Default)]
+pub struct CompressResponse {
+    text: String,
+}
+
+impl CompressRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CompressResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Encode for CompressRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "COMPRESS DEFLATE").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for CompressRequest {
+    type Response = CompressResponse;
+}
+
+impl ExpectedResponseCode for CompressResponse {
+    const CODES: ResponseCodeTuples =
+        &[(206, false, true), (403, false, false), (502, false, false)];
+}
+
+impl Decode for CompressResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Everything after the 206 response is compressed, COMPRESS is no longer advertised
+        if code == 206 {
+            session.set_compressed();
+            session.discard_capabilities();
+        }
+    }
+}
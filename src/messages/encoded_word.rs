@@ -0,0 +1,160 @@
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use encoding_rs::Encoding;
+
+// Some encoders drop the padding of B encoded words
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// Longest encoded text that keeps an UTF-8 B encoded word within 75 characters
+const MAX_ENCODED_BYTES: usize = 45;
+
+// Decodes RFC 2047 encoded words in a header value. Whitespace between adjacent encoded words
+// is dropped, words with unknown charsets or invalid encodings are kept as they are.
+pub fn decode(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut pending: Option<(&'static Encoding, Vec<u8>)> = None;
+    let mut rest = value;
+
+    while let Some(start) = rest.find("=?") {
+        let Some((encoding, bytes, len)) = parse_word(&rest[start..]) else {
+            flush(&mut decoded, &mut pending);
+            decoded.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            continue;
+        };
+
+        let between = &rest[..start];
+
+        // Words of the same charset are joined before decoding, characters may span words
+        match &mut pending {
+            Some((pending_encoding, pending_bytes))
+                if between.trim().is_empty() && *pending_encoding == encoding =>
+            {
+                pending_bytes.extend_from_slice(&bytes);
+            }
+            Some(_) if between.trim().is_empty() => {
+                flush(&mut decoded, &mut pending);
+                pending = Some((encoding, bytes));
+            }
+            _ => {
+                flush(&mut decoded, &mut pending);
+                decoded.push_str(between);
+                pending = Some((encoding, bytes));
+            }
+        }
+
+        rest = &rest[start + len..];
+    }
+
+    flush(&mut decoded, &mut pending);
+    decoded.push_str(rest);
+    decoded
+}
+
+// Encodes a header value for posting. Runs of words that are not plain ASCII are sent as UTF-8
+// B encoded words, everything else is kept, so that addresses stay readable.
+pub fn encode(value: &str) -> String {
+    let words = value.split(' ').collect::<Vec<_>>();
+    let mut encoded = Vec::new();
+    let mut run = Vec::new();
+
+    for word in words {
+        if needs_encoding(word) {
+            run.push(word);
+            continue;
+        }
+
+        if !run.is_empty() {
+            encoded.push(encode_words(&run.join(" ")));
+            run.clear();
+        }
+
+        encoded.push(word.to_string());
+    }
+
+    if !run.is_empty() {
+        encoded.push(encode_words(&run.join(" ")));
+    }
+
+    encoded.join(" ")
+}
+
+fn parse_word(word: &str) -> Option<(&'static Encoding, Vec<u8>, usize)> {
+    let rest = word.strip_prefix("=?")?;
+    let (charset, rest) = rest.split_once('?')?;
+    let (encoding, rest) = rest.split_once('?')?;
+    let end = rest.find("?=")?;
+    let text = &rest[..end];
+
+    if text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    // RFC 2231 allows a language suffix, e.g. `UTF-8*en`
+    let charset = charset.split('*').next()?;
+    let charset = Encoding::for_label(charset.trim().as_bytes())?;
+
+    let bytes = match encoding {
+        "B" | "b" => BASE64.decode(text).ok()?,
+        "Q" | "q" => decode_q(text)?,
+        _ => return None,
+    };
+
+    Some((charset, bytes, word.len() - rest[end + 2..].len()))
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.bytes();
+
+    while let Some(b) = chars.next() {
+        let b = match b {
+            b'_' => b' ',
+            b'=' => {
+                let hex = [chars.next()?, chars.next()?];
+                u8::from_str_radix(str::from_utf8(&hex).ok()?, 16).ok()?
+            }
+            b => b,
+        };
+
+        bytes.push(b);
+    }
+
+    Some(bytes)
+}
+
+fn flush(decoded: &mut String, pending: &mut Option<(&'static Encoding, Vec<u8>)>) {
+    if let Some((encoding, bytes)) = pending.take() {
+        decoded.push_str(&encoding.decode_without_bom_handling(&bytes).0);
+    }
+}
+
+fn needs_encoding(word: &str) -> bool {
+    word.contains("=?") || word.chars().any(|c| !c.is_ascii() || c.is_ascii_control())
+}
+
+// Splits the text at character boundaries into words of at most 75 characters
+fn encode_words(text: &str) -> String {
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+
+    for c in text.chars() {
+        if chunk.len() + c.len_utf8() > MAX_ENCODED_BYTES {
+            words.push(format!("=?UTF-8?B?{}?=", BASE64.encode(&chunk)));
+            chunk.clear();
+        }
+
+        chunk.push(c);
+    }
+
+    if !chunk.is_empty() {
+        words.push(format!("=?UTF-8?B?{}?=", BASE64.encode(&chunk)));
+    }
+
+    words.join(" ")
+}
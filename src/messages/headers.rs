@@ -0,0 +1,286 @@
+use crate::messages::encoded_word;
+use crate::messages::xover::parse_datetime;
+use crate::messages::Decoder;
+use crate::mime::{ContentType, TransferEncoding};
+use crate::Result;
+use chrono::{DateTime, Utc};
+use std::fmt::{Display, Formatter};
+
+// Article headers in the order they were received. Names are matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct Headers {
+    entries: Vec<Field>,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    value: String,
+    // Unfolded value as received, before any charset decoding
+    raw: Vec<u8>,
+}
+
+// Address of a From header, with the display name if present
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mailbox {
+    name: Option<String>,
+    address: String,
+}
+
+// Xref header: the server that assigned the numbers and the number in every group
+#[derive(Debug, Clone)]
+pub struct Xref {
+    server: String,
+    entries: Vec<(String, usize)>,
+}
+
+impl Headers {
+    // Reads header lines up to the empty line separating them from the body, or the end
+    pub(crate) fn decode(bytes: &mut Decoder) -> Result<Self> {
+        let mut headers = Headers::default();
+
+        while let Some(line) = bytes.line() {
+            let line = line.as_slice();
+
+            if line.is_empty() {
+                break;
+            }
+
+            // Folded header, continues the previous one
+            if line.starts_with(b" ") || line.starts_with(b"\t") {
+                if let Some(field) = headers.entries.last_mut() {
+                    field.raw.extend_from_slice(line);
+                }
+
+                continue;
+            }
+
+            if let Some(colon) = line.iter().position(|b| *b == b':') {
+                headers.entries.push(Field {
+                    name: bytes.text(&line[..colon]).trim().to_string(),
+                    value: String::new(),
+                    raw: line[colon + 1..].trim_ascii_start().to_vec(),
+                });
+            }
+        }
+
+        for field in headers.entries.iter_mut() {
+            field.raw.truncate(field.raw.trim_ascii_end().len());
+            field.value = bytes.text(&field.raw).into_owned();
+        }
+
+        Ok(headers)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+    }
+
+    // First value of the header
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.field(name).map(|field| field.value.as_str())
+    }
+
+    // First value of the header as received, before any charset decoding
+    pub fn get_raw(&self, name: &str) -> Option<&[u8]> {
+        self.field(name).map(|field| field.raw.as_slice())
+    }
+
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |field| field.name.eq_ignore_ascii_case(name))
+            .map(|field| field.value.as_str())
+    }
+
+    fn field(&self, name: &str) -> Option<&Field> {
+        self.entries
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+    }
+
+    // First value of the header with RFC 2047 encoded words decoded
+    pub fn get_decoded(&self, name: &str) -> Option<String> {
+        self.get(name).map(encoded_word::decode)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn message_id(&self) -> Option<&str> {
+        self.get("Message-ID").map(strip_id)
+    }
+
+    pub fn subject(&self) -> Option<&str> {
+        self.get("Subject")
+    }
+
+    pub fn subject_decoded(&self) -> Option<String> {
+        self.get_decoded("Subject")
+    }
+
+    pub fn from(&self) -> Option<Mailbox> {
+        self.get("From").and_then(Mailbox::parse)
+    }
+
+    pub fn date(&self) -> Option<DateTime<Utc>> {
+        self.get("Date").and_then(parse_datetime)
+    }
+
+    pub fn newsgroups(&self) -> Vec<&str> {
+        self.get("Newsgroups")
+            .map(|groups| {
+                groups
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|group| !group.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Message-ids of the References header, oldest first
+    pub fn references(&self) -> Vec<&str> {
+        self.get("References")
+            .map(parse_references)
+            .unwrap_or_default()
+    }
+
+    pub fn xref(&self) -> Option<Xref> {
+        self.get("Xref").and_then(Xref::parse)
+    }
+
+    // Hosts the article passed, most recent first
+    pub fn path(&self) -> Vec<&str> {
+        self.get("Path")
+            .map(|path| path.split('!').map(str::trim).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn lines(&self) -> Option<usize> {
+        self.get("Lines")
+            .and_then(|lines| lines.trim().parse().ok())
+    }
+
+    pub fn bytes(&self) -> Option<usize> {
+        self.get("Bytes")
+            .and_then(|bytes| bytes.trim().parse().ok())
+    }
+
+    pub fn content_type(&self) -> Option<ContentType> {
+        self.get("Content-Type").and_then(ContentType::parse)
+    }
+
+    // Content-Transfer-Encoding, 7bit if absent
+    pub fn transfer_encoding(&self) -> TransferEncoding {
+        self.get("Content-Transfer-Encoding")
+            .map(TransferEncoding::parse)
+            .unwrap_or(TransferEncoding::SevenBit)
+    }
+}
+
+impl Mailbox {
+    // Parses `Name <address>`, `address (Name)` and bare addresses
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        if let Some((name, rest)) = value.rsplit_once('<') {
+            let address = rest.split_once('>')?.0.trim();
+            let name = name.trim().trim_matches('"').trim();
+
+            return Some(Self::new(name, address));
+        }
+
+        if let Some((address, rest)) = value.split_once('(') {
+            let name = rest.rsplit_once(')').map(|(name, _)| name).unwrap_or(rest);
+
+            return Some(Self::new(name.trim(), address.trim()));
+        }
+
+        (!value.is_empty()).then(|| Self::new("", value))
+    }
+
+    fn new(name: &str, address: &str) -> Self {
+        Self {
+            name: (!name.is_empty()).then(|| name.to_string()),
+            address: address.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    // Display name with RFC 2047 encoded words decoded
+    pub fn name_decoded(&self) -> Option<String> {
+        self.name.as_deref().map(encoded_word::decode)
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl Display for Mailbox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} <{}>", name, self.address),
+            None => write!(f, "{}", self.address),
+        }
+    }
+}
+
+impl Xref {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let server = parts.next()?.to_string();
+
+        let entries = parts
+            .filter_map(|entry| {
+                let (group, number) = entry.rsplit_once(':')?;
+                Some((group.to_string(), number.parse().ok()?))
+            })
+            .collect();
+
+        Some(Self { server, entries })
+    }
+
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    // Group and article number pairs
+    pub fn entries(&self) -> &[(String, usize)] {
+        &self.entries
+    }
+
+    pub fn number(&self, group: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|(g, _)| g == group)
+            .map(|(_, number)| *number)
+    }
+}
+
+// Message-ids of a References header or overview field, without angle brackets
+pub(crate) fn parse_references(references: &str) -> Vec<&str> {
+    references
+        .split('<')
+        .filter_map(|id| id.split_once('>').map(|(id, _)| id))
+        .collect()
+}
+
+pub(crate) fn strip_id(id: &str) -> &str {
+    id.trim().trim_start_matches('<').trim_end_matches('>')
+}
@@ -0,0 +1,71 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::payload::ArticlePayload;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub struct IhaveRequest {
+    id: String,
+    payload: ArticlePayload,
+}
+
+#[derive(Default)]
+pub struct IhaveResponse {
+    text: String,
+}
+
+impl IhaveRequest {
+    pub fn new(id: impl ToString, article: impl Into<ArticlePayload>) -> Self {
+        Self {
+            id: id.to_string(),
+            payload: article.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub(crate) fn payload(&self) -> &ArticlePayload {
+        &self.payload
+    }
+}
+
+impl IhaveResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Encode for IhaveRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "IHAVE <{}>", self.id).map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for IhaveRequest {
+    type Response = IhaveResponse;
+}
+
+impl ExpectedResponseCode for IhaveResponse {
+    const CODES: ResponseCodeTuples = &[
+        (235, false, true),
+        (335, false, true),
+        (435, false, false),
+        (436, false, false),
+        (437, false, false),
+    ];
+}
+
+impl Decode for IhaveResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+}
@@ -1,243 +1,725 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::{Decode, Decoder, ResponseCodeTuples};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use std::io::Write;
-use std::str::FromStr;
-
-pub struct ListRequest {
-    keyword: Option<String>,
-    arg: Option<String>,
-}
-
-#[derive(Default)]
-pub struct GroupListResponse {
-    groups: Vec<Group>,
-}
-
-#[derive(Default)]
-pub struct GroupTimesResponse {
-    groups: Vec<GroupTimes>,
-}
-
-#[derive(Default)]
-pub struct GroupNewsgroupResponse {
-    groups: Vec<GroupNewsgroup>,
-}
-
-#[derive(Debug, Copy, Clone)]
-pub enum GroupStatus {
-    PostingPermitted,
-    PostingNotPermitted,
-    Moderated,
-}
-
-pub struct Group {
-    name: String,
-    high: usize,
-    low: usize,
-    status: GroupStatus,
-}
-
-pub struct GroupTimes {
-    name: String,
-    age: usize,
-    creator: String,
-}
-
-pub struct GroupNewsgroup {
-    name: String,
-    description: String,
-}
-
-impl ListRequest {
-    pub fn new(keyword: Option<impl ToString>, arg: Option<impl ToString>) -> Self {
-        Self {
-            keyword: keyword.map(|k| k.to_string()),
-            arg: arg.map(|a| a.to_string()),
-        }
-    }
-}
-
-impl GroupListResponse {
-    pub fn groups(&self) -> &Vec<Group> {
-        &self.groups
-    }
-}
-
-impl GroupTimesResponse {
-    pub fn groups(&self) -> &Vec<GroupTimes> {
-        &self.groups
-    }
-}
-
-impl GroupNewsgroupResponse {
-    pub fn groups(&self) -> &Vec<GroupNewsgroup> {
-        &self.groups
-    }
-}
-
-impl Group {
-    fn new(name: String, high: usize, low: usize, status: GroupStatus) -> Self {
-        Self {
-            name,
-            high,
-            low,
-            status,
-        }
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    pub fn high(&self) -> usize {
-        self.high
-    }
-
-    pub fn low(&self) -> usize {
-        self.low
-    }
-
-    pub fn status(&self) -> GroupStatus {
-        self.status
-    }
-}
-
-impl GroupTimes {
-    fn new(name: String, age: usize, creator: String) -> Self {
-        Self { name, age, creator }
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    pub fn age(&self) -> usize {
-        self.age
-    }
-
-    pub fn creator(&self) -> &str {
-        &self.creator
-    }
-}
-
-impl GroupNewsgroup {
-    fn new(name: String, description: String) -> Self {
-        Self { name, description }
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    pub fn description(&self) -> &str {
-        &self.description
-    }
-}
-
-impl Encode for ListRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        write!(bytes.writer(), "LIST").map_err(Error::encode)?;
-
-        if let Some(keyword) = &self.keyword {
-            write!(bytes.writer(), " {}", keyword).map_err(Error::encode)?;
-
-            if let Some(arg) = &self.arg {
-                write!(bytes.writer(), " {}", arg).map_err(Error::encode)?;
-            }
-        }
-
-        Ok(())
-    }
-}
-
-impl ExpectedResponse for ListRequest {
-    type Response = GroupListResponse;
-}
-
-impl ExpectedResponseCode for GroupListResponse {
-    const CODES: ResponseCodeTuples = &[(215, true, true)];
-}
-
-impl Decode for GroupListResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        // Discard first line
-        let _ = bytes.line();
-
-        while let Some(mut line) = bytes.line() {
-            let name = line.get()?;
-            let high = line.get()?;
-            let low = line.get()?;
-            let status = line.get()?;
-
-            self.groups.push(Group::new(name, high, low, status));
-        }
-
-        Ok(())
-    }
-}
-
-impl ExpectedResponseCode for GroupTimesResponse {
-    const CODES: ResponseCodeTuples = &[(215, true, true)];
-}
-
-impl Decode for GroupTimesResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        // Discard first line
-        let _ = bytes.line();
-
-        while let Some(mut line) = bytes.line() {
-            let name = line.get()?;
-            let age = line.get()?;
-            let creator = line.get()?;
-
-            self.groups.push(GroupTimes::new(name, age, creator));
-        }
-
-        Ok(())
-    }
-}
-
-impl ExpectedResponseCode for GroupNewsgroupResponse {
-    const CODES: ResponseCodeTuples = &[(215, true, true)];
-}
-
-impl Decode for GroupNewsgroupResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        // Discard first line
-        let _ = bytes.line();
-
-        while let Some(mut line) = bytes.line() {
-            let name = line.get()?;
-            let description = line.get()?;
-
-            self.groups.push(GroupNewsgroup::new(name, description));
-        }
-
-        Ok(())
-    }
-}
-
-impl FromStr for GroupStatus {
-    type Err = Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "y" => Ok(GroupStatus::PostingPermitted),
-            "n" => Ok(GroupStatus::PostingNotPermitted),
-            "m" => Ok(GroupStatus::Moderated),
-            _ => Err(Error::DecodeFromStr),
-        }
-    }
-}
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::wildmat::Wildmat;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+// 503 is returned for keywords the server does not support
+const LIST_CODES: ResponseCodeTuples = &[(215, true, true), (503, false, false)];
+
+// LIST request, typed by the response its keyword produces. Use the keyword constructors
+// (`ListRequest::active()`, `ListRequest::motd()`, ...) to get the matching response type.
+pub struct ListRequest<R = GroupListResponse> {
+    keyword: Option<String>,
+    arg: Option<String>,
+    response: PhantomData<R>,
+}
+
+pub enum HeadersType {
+    Any,
+    MessageId,
+    Range,
+}
+
+#[derive(Default)]
+pub struct GroupListResponse {
+    groups: Vec<Group>,
+}
+
+#[derive(Default)]
+pub struct GroupTimesResponse {
+    groups: Vec<GroupTimes>,
+}
+
+#[derive(Default)]
+pub struct GroupNewsgroupResponse {
+    groups: Vec<GroupNewsgroup>,
+}
+
+#[derive(Default)]
+pub struct GroupCountsResponse {
+    groups: Vec<GroupCount>,
+}
+
+#[derive(Default)]
+pub struct DistribPatsResponse {
+    patterns: Vec<DistribPat>,
+}
+
+#[derive(Default)]
+pub struct DistributionsResponse {
+    distributions: Vec<Distribution>,
+}
+
+#[derive(Default)]
+pub struct HeadersResponse {
+    headers: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct MotdResponse {
+    lines: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct SubscriptionsResponse {
+    groups: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ModeratorsResponse {
+    moderators: Vec<Moderator>,
+}
+
+#[derive(Default)]
+pub struct OverviewFmtResponse {
+    fields: Vec<String>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum GroupStatus {
+    PostingPermitted,
+    PostingNotPermitted,
+    Moderated,
+}
+
+pub struct Group {
+    name: String,
+    high: usize,
+    low: usize,
+    status: GroupStatus,
+}
+
+pub struct GroupTimes {
+    name: String,
+    age: usize,
+    creator: String,
+}
+
+pub struct GroupNewsgroup {
+    name: String,
+    description: String,
+}
+
+pub struct GroupCount {
+    name: String,
+    high: usize,
+    low: usize,
+    count: usize,
+    status: GroupStatus,
+}
+
+pub struct DistribPat {
+    weight: usize,
+    wildmat: String,
+    distribution: String,
+}
+
+pub struct Distribution {
+    name: String,
+    description: String,
+}
+
+pub struct Moderator {
+    wildmat: String,
+    address: String,
+}
+
+impl ListRequest {
+    pub fn new(keyword: Option<impl ToString>, arg: Option<impl ToString>) -> Self {
+        Self {
+            keyword: keyword.map(|k| k.to_string()),
+            arg: arg.map(|a| a.to_string()),
+            response: PhantomData,
+        }
+    }
+
+    pub fn active() -> ListRequest<GroupListResponse> {
+        ListRequest::typed("ACTIVE", None)
+    }
+
+    pub fn active_matching(wildmat: impl ToString) -> ListRequest<GroupListResponse> {
+        ListRequest::typed("ACTIVE", Some(wildmat.to_string()))
+    }
+
+    pub fn active_times() -> ListRequest<GroupTimesResponse> {
+        ListRequest::typed("ACTIVE.TIMES", None)
+    }
+
+    pub fn active_times_matching(wildmat: impl ToString) -> ListRequest<GroupTimesResponse> {
+        ListRequest::typed("ACTIVE.TIMES", Some(wildmat.to_string()))
+    }
+
+    pub fn newsgroups() -> ListRequest<GroupNewsgroupResponse> {
+        ListRequest::typed("NEWSGROUPS", None)
+    }
+
+    pub fn newsgroups_matching(wildmat: impl ToString) -> ListRequest<GroupNewsgroupResponse> {
+        ListRequest::typed("NEWSGROUPS", Some(wildmat.to_string()))
+    }
+
+    pub fn counts() -> ListRequest<GroupCountsResponse> {
+        ListRequest::typed("COUNTS", None)
+    }
+
+    pub fn counts_matching(wildmat: impl ToString) -> ListRequest<GroupCountsResponse> {
+        ListRequest::typed("COUNTS", Some(wildmat.to_string()))
+    }
+
+    pub fn distrib_pats() -> ListRequest<DistribPatsResponse> {
+        ListRequest::typed("DISTRIB.PATS", None)
+    }
+
+    pub fn distributions() -> ListRequest<DistributionsResponse> {
+        ListRequest::typed("DISTRIBUTIONS", None)
+    }
+
+    pub fn headers(_type: HeadersType) -> ListRequest<HeadersResponse> {
+        let arg = match _type {
+            HeadersType::Any => None,
+            HeadersType::MessageId => Some("MSGID".to_string()),
+            HeadersType::Range => Some("RANGE".to_string()),
+        };
+
+        ListRequest::typed("HEADERS", arg)
+    }
+
+    pub fn motd() -> ListRequest<MotdResponse> {
+        ListRequest::typed("MOTD", None)
+    }
+
+    pub fn subscriptions() -> ListRequest<SubscriptionsResponse> {
+        ListRequest::typed("SUBSCRIPTIONS", None)
+    }
+
+    pub fn moderators() -> ListRequest<ModeratorsResponse> {
+        ListRequest::typed("MODERATORS", None)
+    }
+
+    pub fn overview_fmt() -> ListRequest<OverviewFmtResponse> {
+        ListRequest::typed("OVERVIEW.FMT", None)
+    }
+}
+
+impl<R> ListRequest<R> {
+    fn typed(keyword: &str, arg: Option<String>) -> Self {
+        Self {
+            keyword: Some(keyword.to_string()),
+            arg,
+            response: PhantomData,
+        }
+    }
+}
+
+impl GroupListResponse {
+    pub fn groups(&self) -> &Vec<Group> {
+        &self.groups
+    }
+
+    pub fn matching<'a>(&'a self, wildmat: &'a Wildmat) -> impl Iterator<Item = &'a Group> {
+        self.groups.iter().filter(|g| wildmat.matches(g.name()))
+    }
+}
+
+impl GroupTimesResponse {
+    pub fn groups(&self) -> &Vec<GroupTimes> {
+        &self.groups
+    }
+}
+
+impl GroupNewsgroupResponse {
+    pub fn groups(&self) -> &Vec<GroupNewsgroup> {
+        &self.groups
+    }
+}
+
+impl GroupCountsResponse {
+    pub fn groups(&self) -> &Vec<GroupCount> {
+        &self.groups
+    }
+}
+
+impl DistribPatsResponse {
+    pub fn patterns(&self) -> &Vec<DistribPat> {
+        &self.patterns
+    }
+}
+
+impl DistributionsResponse {
+    pub fn distributions(&self) -> &Vec<Distribution> {
+        &self.distributions
+    }
+}
+
+impl HeadersResponse {
+    // Header names and metadata items (`:bytes`, `:lines`), a single `:` stands for any header
+    pub fn headers(&self) -> &Vec<String> {
+        &self.headers
+    }
+}
+
+impl MotdResponse {
+    pub fn lines(&self) -> &Vec<String> {
+        &self.lines
+    }
+}
+
+impl SubscriptionsResponse {
+    pub fn groups(&self) -> &Vec<String> {
+        &self.groups
+    }
+}
+
+impl ModeratorsResponse {
+    pub fn moderators(&self) -> &Vec<Moderator> {
+        &self.moderators
+    }
+}
+
+impl OverviewFmtResponse {
+    pub fn fields(&self) -> &Vec<String> {
+        &self.fields
+    }
+}
+
+impl Group {
+    fn new(name: String, high: usize, low: usize, status: GroupStatus) -> Self {
+        Self {
+            name,
+            high,
+            low,
+            status,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn high(&self) -> usize {
+        self.high
+    }
+
+    pub fn low(&self) -> usize {
+        self.low
+    }
+
+    pub fn status(&self) -> GroupStatus {
+        self.status
+    }
+
+    // Decodes a `name high low status` line as used by LIST ACTIVE and NEWGROUPS
+    pub(crate) fn decode(line: &mut Decoder) -> Result<Self> {
+        let name = line.get()?;
+        let high = line.get()?;
+        let low = line.get()?;
+        let status = line.get()?;
+
+        Ok(Group::new(name, high, low, status))
+    }
+}
+
+impl GroupTimes {
+    fn new(name: String, age: usize, creator: String) -> Self {
+        Self { name, age, creator }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn age(&self) -> usize {
+        self.age
+    }
+
+    pub fn creator(&self) -> &str {
+        &self.creator
+    }
+}
+
+impl GroupNewsgroup {
+    fn new(name: String, description: String) -> Self {
+        Self { name, description }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl GroupCount {
+    fn new(name: String, high: usize, low: usize, count: usize, status: GroupStatus) -> Self {
+        Self {
+            name,
+            high,
+            low,
+            count,
+            status,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn high(&self) -> usize {
+        self.high
+    }
+
+    pub fn low(&self) -> usize {
+        self.low
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn status(&self) -> GroupStatus {
+        self.status
+    }
+}
+
+impl DistribPat {
+    fn new(weight: usize, wildmat: String, distribution: String) -> Self {
+        Self {
+            weight,
+            wildmat,
+            distribution,
+        }
+    }
+
+    pub fn weight(&self) -> usize {
+        self.weight
+    }
+
+    pub fn wildmat(&self) -> &str {
+        &self.wildmat
+    }
+
+    pub fn distribution(&self) -> &str {
+        &self.distribution
+    }
+}
+
+impl Distribution {
+    fn new(name: String, description: String) -> Self {
+        Self { name, description }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Moderator {
+    fn new(wildmat: String, address: String) -> Self {
+        Self { wildmat, address }
+    }
+
+    pub fn wildmat(&self) -> &str {
+        &self.wildmat
+    }
+
+    // Submission address, `%s` is replaced by the group name with dots turned into dashes
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl<R> Encode for ListRequest<R> {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "LIST").map_err(Error::encode)?;
+
+        if let Some(keyword) = &self.keyword {
+            write!(bytes.writer(), " {}", keyword).map_err(Error::encode)?;
+
+            if let Some(arg) = &self.arg {
+                write!(bytes.writer(), " {}", arg).map_err(Error::encode)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R> ExpectedResponse for ListRequest<R> {
+    type Response = R;
+}
+
+impl ExpectedResponseCode for GroupListResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for GroupListResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            self.groups.push(Group::decode(&mut line)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for GroupTimesResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for GroupTimesResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let name = line.get()?;
+            let age = line.get()?;
+            let creator = line.get()?;
+
+            self.groups.push(GroupTimes::new(name, age, creator));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for GroupNewsgroupResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for GroupNewsgroupResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let (name, description) = split_whitespace(&mut line)?;
+
+            self.groups.push(GroupNewsgroup::new(name, description));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for GroupCountsResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for GroupCountsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let name = line.get()?;
+            let high = line.get()?;
+            let low = line.get()?;
+            let count = line.get()?;
+            let status = line.get()?;
+
+            self.groups
+                .push(GroupCount::new(name, high, low, count, status));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for DistribPatsResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for DistribPatsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let weight = line.get_with_delimiter(b":")?;
+            let wildmat = line.get_with_delimiter(b":")?;
+            let distribution = line.all()?;
+
+            self.patterns
+                .push(DistribPat::new(weight, wildmat, distribution));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for DistributionsResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for DistributionsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let (name, description) = split_whitespace(&mut line)?;
+
+            self.distributions
+                .push(Distribution::new(name, description));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for HeadersResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for HeadersResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            self.headers.push(line.trim().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for MotdResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for MotdResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            self.lines.push(line);
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for SubscriptionsResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for SubscriptionsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            self.groups.push(line.trim().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for ModeratorsResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for ModeratorsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            let wildmat = line.get_with_delimiter(b":")?;
+            let address = line.all()?;
+
+            self.moderators.push(Moderator::new(wildmat, address));
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponseCode for OverviewFmtResponse {
+    const CODES: ResponseCodeTuples = LIST_CODES;
+}
+
+impl Decode for OverviewFmtResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            self.fields.push(line.trim().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// Splits a `name description` line at the first run of spaces or tabs
+fn split_whitespace(line: &mut Decoder) -> Result<(String, String)> {
+    let line = line.all::<String>()?;
+
+    let (name, description) = line.split_once([' ', '\t']).unwrap_or((line.as_str(), ""));
+
+    Ok((name.to_string(), description.trim().to_string()))
+}
+
+impl FromStr for GroupStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "y" => Ok(GroupStatus::PostingPermitted),
+            "n" => Ok(GroupStatus::PostingNotPermitted),
+            "m" => Ok(GroupStatus::Moderated),
+            _ => Err(Error::DecodeFromStr),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::xover::RangeType;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub struct ListgroupRequest {
+    name: String,
+    range: Option<RangeType>,
+}
+
+#[derive(Default)]
+pub struct ListgroupResponse {
+    number: usize,
+    low: usize,
+    high: usize,
+    group: String,
+    articles: Vec<usize>,
+}
+
+impl ListgroupRequest {
+    pub fn new(name: impl ToString, range: Option<RangeType>) -> Self {
+        Self {
+            name: name.to_string(),
+            range,
+        }
+    }
+}
+
+impl ListgroupResponse {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn low(&self) -> usize {
+        self.low
+    }
+
+    pub fn high(&self) -> usize {
+        self.high
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    // Numbers of the articles that exist in the group (or the requested range), ascending
+    pub fn articles(&self) -> &Vec<usize> {
+        &self.articles
+    }
+}
+
+impl Encode for ListgroupRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "LISTGROUP {}", self.name).map_err(Error::encode)?;
+
+        if let Some(range) = &self.range {
+            write!(bytes.writer(), " {}", range).map_err(Error::encode)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ExpectedResponse for ListgroupRequest {
+    type Response = ListgroupResponse;
+}
+
+impl ExpectedResponseCode for ListgroupResponse {
+    const CODES: ResponseCodeTuples =
+        &[(211, true, true), (411, false, false), (412, false, false)];
+}
+
+impl Decode for ListgroupResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if code != 211 {
+            return Ok(());
+        }
+
+        let Some(mut line) = bytes.line() else {
+            return Err(Error::DecodeNeedMoreBytes);
+        };
+
+        self.number = line.get()?;
+        self.low = line.get()?;
+        self.high = line.get()?;
+        self.group = line.get()?;
+
+        while let Some(mut line) = bytes.line() {
+            self.articles.push(line.all()?);
+        }
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        if code == 211 {
+            session.select_group(&self.group, (self.number > 0).then_some(self.low));
+        }
+    }
+}
@@ -1,72 +1,102 @@
-use crate::decoder::{Decode, Decoder, ExpectedResponse, ExpectedResponseCode, ResponseCodeTuples};
-use crate::{Error, Result};
-use std::ops::Deref;
-
-pub mod article;
-pub mod auth;
-pub mod capabilities;
-pub mod date;
-pub mod greeting;
-pub mod group;
-pub mod head;
-pub mod list;
-pub mod newsgroups;
-pub mod xover;
-
-pub use greeting::*;
-
-#[derive(Default)]
-pub struct Response<T> {
-    code: u16,
-    kind: T,
-}
-
-impl<T> Response<T> {
-    pub fn code(&self) -> u16 {
-        self.code
-    }
-}
-
-impl<T> Response<T>
-where
-    T: ExpectedResponseCode,
-{
-    pub fn ok(&self) -> bool {
-        self.kind.ok(self.code)
-    }
-}
-
-impl<T> ExpectedResponse for Response<T> {
-    type Response = T;
-}
-
-impl<T> ExpectedResponseCode for Response<T> {
-    const CODES: ResponseCodeTuples = &[(0, false, false)];
-}
-
-impl<T> Decode for Response<T>
-where
-    T: Decode + ExpectedResponseCode,
-{
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()> {
-        if bytes.len() < 3 {
-            return Err(Error::DecodeNeedMoreBytes);
-        };
-
-        // We already have set a code and the buffer has advanced, do not attempt to read code
-        // another time
-        if self.code == 0 {
-            self.code = bytes.get()?;
-        }
-
-        self.kind.decode(bytes, self.code)
-    }
-}
-
-impl<T> Deref for Response<T> {
-    type Target = T;
-
-    fn deref(&self) -> &Self::Target {
-        &self.kind
-    }
-}
+use crate::decoder::{Decode, Decoder, ExpectedResponse, ExpectedResponseCode, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use std::ops::Deref;
+
+pub mod article;
+pub mod auth;
+pub mod capabilities;
+pub mod compress;
+pub mod date;
+pub mod encoded_word;
+pub mod greeting;
+pub mod group;
+pub mod head;
+pub mod headers;
+pub mod ihave;
+pub mod list;
+pub mod listgroup;
+pub mod mode;
+pub mod navigation;
+pub mod newnews;
+pub mod newsgroups;
+pub mod payload;
+pub mod quit;
+pub mod stat;
+pub mod stream;
+pub mod xover;
+
+pub use greeting::*;
+
+#[derive(Default)]
+pub struct Response<T> {
+    code: u16,
+    kind: T,
+}
+
+impl<T> Response<T> {
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    pub fn into_inner(self) -> T {
+        self.kind
+    }
+}
+
+impl<T> Response<T>
+where
+    T: ExpectedResponseCode,
+{
+    pub fn ok(&self) -> bool {
+        self.kind.ok(self.code)
+    }
+}
+
+impl<T> ExpectedResponse for Response<T> {
+    type Response = T;
+}
+
+impl<T> ExpectedResponseCode for Response<T> {
+    const CODES: ResponseCodeTuples = &[(0, false, false)];
+}
+
+impl<T> Decode for Response<T>
+where
+    T: Decode + ExpectedResponseCode,
+{
+    // Completeness is established by the framing of the kind, which may not be line based
+    fn decode(&mut self, bytes: &mut Decoder, code: u16) -> Result<()> {
+        self.decoder(bytes, code)
+    }
+
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()> {
+        if bytes.len() < 3 {
+            return Err(Error::DecodeNeedMoreBytes);
+        };
+
+        // We already have set a code and the buffer has advanced, do not attempt to read code
+        // another time
+        if self.code == 0 {
+            self.code = bytes.get()?;
+        }
+
+        self.kind.decode(bytes, self.code)
+    }
+
+    fn frame(&mut self, bytes: &Decoder) -> Result<Option<usize>> {
+        self.kind.frame(bytes)
+    }
+
+    fn apply(&self, session: &mut Session, _: u16) {
+        self.kind.apply(session, self.code)
+    }
+}
+
+impl<T> Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.kind
+    }
+}
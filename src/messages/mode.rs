@@ -0,0 +1,113 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::{Mode, Session};
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+#[derive(Default)]
+pub struct ModeReaderRequest;
+
+#[derive(Default)]
+pub struct ModeReaderResponse {
+    text: String,
+}
+
+#[derive(Default)]
+pub struct ModeStreamRequest;
+
+#[derive(Default)]
+pub struct ModeStreamResponse {
+    text: String,
+}
+
+impl ModeReaderRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ModeReaderResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl ModeStreamRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ModeStreamResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Encode for ModeReaderRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "MODE READER").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for ModeReaderRequest {
+    type Response = ModeReaderResponse;
+}
+
+impl ExpectedResponseCode for ModeReaderResponse {
+    const CODES: ResponseCodeTuples =
+        &[(200, false, true), (201, false, true), (502, false, false)];
+}
+
+impl Decode for ModeReaderResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Capabilities change after switching to reader mode
+        if code == 200 || code == 201 {
+            session.set_mode(Mode::Reader);
+            session.discard_capabilities();
+        }
+    }
+}
+
+impl Encode for ModeStreamRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "MODE STREAM").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for ModeStreamRequest {
+    type Response = ModeStreamResponse;
+}
+
+impl ExpectedResponseCode for ModeStreamResponse {
+    const CODES: ResponseCodeTuples =
+        &[(203, false, true), (500, false, false), (501, false, false)];
+}
+
+impl Decode for ModeStreamResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        if code == 203 {
+            session.set_mode(Mode::Transit);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+#[derive(Default)]
+pub struct NextRequest;
+
+#[derive(Default)]
+pub struct LastRequest;
+
+#[derive(Default)]
+pub struct NavigationResponse {
+    number: usize,
+    id: String,
+}
+
+impl NextRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LastRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl NavigationResponse {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Encode for NextRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "NEXT").map_err(Error::encode)
+    }
+}
+
+impl Encode for LastRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "LAST").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for NextRequest {
+    type Response = NavigationResponse;
+}
+
+impl ExpectedResponse for LastRequest {
+    type Response = NavigationResponse;
+}
+
+impl ExpectedResponseCode for NavigationResponse {
+    const CODES: ResponseCodeTuples = &[
+        (223, false, true),
+        (412, false, false),
+        (420, false, false),
+        (421, false, false),
+        (422, false, false),
+    ];
+}
+
+impl Decode for NavigationResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if code != 223 {
+            return Ok(());
+        }
+
+        self.number = bytes.get()?;
+
+        let id = bytes.get::<String>()?;
+        self.id = id.trim_start_matches('<').trim_end_matches('>').to_string();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        if code == 223 {
+            session.select_article(self.number);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::newsgroups::encode_since;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use std::io::Write;
+
+pub struct NewnewsRequest {
+    wildmat: String,
+    datetime: chrono::DateTime<Utc>,
+    gmt: bool,
+    distributions: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct NewnewsResponse {
+    ids: Vec<String>,
+}
+
+impl NewnewsRequest {
+    pub fn new(wildmat: impl ToString, datetime: chrono::DateTime<Utc>) -> Self {
+        Self {
+            wildmat: wildmat.to_string(),
+            datetime,
+            gmt: true,
+            distributions: Vec::new(),
+        }
+    }
+
+    // Sends the date and time as GMT (the default). Without it, servers interpret them in
+    // their local time zone.
+    pub fn gmt(mut self, gmt: bool) -> Self {
+        self.gmt = gmt;
+        self
+    }
+
+    // Restricts the result to the given distributions (RFC 977)
+    pub fn distributions(mut self, distributions: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.distributions = distributions.into_iter().map(|d| d.to_string()).collect();
+        self
+    }
+}
+
+impl NewnewsResponse {
+    pub fn ids(&self) -> &Vec<String> {
+        &self.ids
+    }
+}
+
+impl Encode for NewnewsRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        self.encoder_with(bytes, &Session::default())
+    }
+
+    // The date is translated into server time once the clock skew has been measured
+    fn encoder_with(&self, bytes: &mut BytesMut, session: &Session) -> Result<()> {
+        write!(bytes.writer(), "NEWNEWS {}", self.wildmat).map_err(Error::encode)?;
+
+        let datetime = session.server_time(self.datetime);
+        encode_since(bytes, &datetime, self.gmt, &self.distributions)
+    }
+}
+
+impl ExpectedResponse for NewnewsRequest {
+    type Response = NewnewsResponse;
+}
+
+impl ExpectedResponseCode for NewnewsResponse {
+    const CODES: ResponseCodeTuples = &[(230, true, true)];
+}
+
+impl Decode for NewnewsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Ok(Some(line)) = bytes.get_line() {
+            let id = line.trim();
+            self.ids
+                .push(id.trim_start_matches('<').trim_end_matches('>').to_string());
+        }
+
+        Ok(())
+    }
+}
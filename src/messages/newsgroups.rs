@@ -1,61 +1,112 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::{Decode, Decoder, ResponseCodeTuples};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use chrono::Utc;
-use std::io::Write;
-
-pub struct NewsgroupsRequest {
-    datetime: chrono::DateTime<Utc>,
-}
-
-#[derive(Default)]
-pub struct NewsgroupsResponse {
-    groups: Vec<String>,
-}
-
-impl NewsgroupsRequest {
-    pub fn new(datetime: chrono::DateTime<Utc>) -> Self {
-        Self { datetime }
-    }
-}
-
-impl NewsgroupsResponse {
-    pub fn groups(&self) -> &Vec<String> {
-        &self.groups
-    }
-}
-
-impl Encode for NewsgroupsRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        let date = self.datetime.format("%Y%m%d");
-        let time = self.datetime.format("%H%M%S");
-
-        write!(bytes.writer(), "NEWGROUPS {} {}", date, time).map_err(Error::encode)
-    }
-}
-
-impl ExpectedResponse for NewsgroupsRequest {
-    type Response = NewsgroupsResponse;
-}
-
-impl ExpectedResponseCode for NewsgroupsResponse {
-    const CODES: ResponseCodeTuples = &[(231, true, true)];
-}
-
-impl Decode for NewsgroupsResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        // Discard first line
-        let _ = bytes.line();
-
-        while let Ok(Some(line)) = bytes.get_line() {
-            self.groups.push(line);
-        }
-
-        Ok(())
-    }
-}
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::list::Group;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use std::io::Write;
+
+pub struct NewsgroupsRequest {
+    datetime: chrono::DateTime<Utc>,
+    gmt: bool,
+    distributions: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct NewsgroupsResponse {
+    groups: Vec<Group>,
+}
+
+impl NewsgroupsRequest {
+    pub fn new(datetime: chrono::DateTime<Utc>) -> Self {
+        Self {
+            datetime,
+            gmt: true,
+            distributions: Vec::new(),
+        }
+    }
+
+    // Sends the date and time as GMT (the default). Without it, servers interpret them in
+    // their local time zone.
+    pub fn gmt(mut self, gmt: bool) -> Self {
+        self.gmt = gmt;
+        self
+    }
+
+    // Restricts the result to the given distributions (RFC 977)
+    pub fn distributions(mut self, distributions: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.distributions = distributions.into_iter().map(|d| d.to_string()).collect();
+        self
+    }
+}
+
+impl NewsgroupsResponse {
+    pub fn groups(&self) -> &Vec<Group> {
+        &self.groups
+    }
+}
+
+impl Encode for NewsgroupsRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        self.encoder_with(bytes, &Session::default())
+    }
+
+    // The date is translated into server time once the clock skew has been measured
+    fn encoder_with(&self, bytes: &mut BytesMut, session: &Session) -> Result<()> {
+        write!(bytes.writer(), "NEWGROUPS").map_err(Error::encode)?;
+
+        let datetime = session.server_time(self.datetime);
+        encode_since(bytes, &datetime, self.gmt, &self.distributions)
+    }
+}
+
+impl ExpectedResponse for NewsgroupsRequest {
+    type Response = NewsgroupsResponse;
+}
+
+impl ExpectedResponseCode for NewsgroupsResponse {
+    const CODES: ResponseCodeTuples = &[(231, true, true)];
+}
+
+impl Decode for NewsgroupsResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        // Discard first line
+        let _ = bytes.line();
+
+        while let Some(mut line) = bytes.line() {
+            self.groups.push(Group::decode(&mut line)?);
+        }
+
+        Ok(())
+    }
+}
+
+// Encodes the date, time, GMT and distribution arguments shared by NEWGROUPS and NEWNEWS
+pub(crate) fn encode_since(
+    bytes: &mut BytesMut,
+    datetime: &chrono::DateTime<Utc>,
+    gmt: bool,
+    distributions: &[String],
+) -> Result<()> {
+    let date = datetime.format("%Y%m%d");
+    let time = datetime.format("%H%M%S");
+
+    write!(bytes.writer(), " {} {}", date, time).map_err(Error::encode)?;
+
+    if gmt {
+        write!(bytes.writer(), " GMT").map_err(Error::encode)?;
+    }
+
+    if !distributions.is_empty() {
+        write!(bytes.writer(), " <{}>", distributions.join(",")).map_err(Error::encode)?;
+    }
+
+    Ok(())
+}
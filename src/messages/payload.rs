@@ -0,0 +1,125 @@
+use crate::decoder::decoder::Encode;
+use crate::messages::encoded_word;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+// Article as it is sent to a server, terminated by a single dot line
+pub struct ArticlePayload {
+    bytes: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct NewArticle {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl ArticlePayload {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            bytes: bytes.into(),
+        }
+    }
+}
+
+impl NewArticle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn header(mut self, name: impl ToString, value: impl ToString) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    // Adds a header, encoding non-ASCII words as RFC 2047 encoded words
+    pub fn encoded_header(self, name: impl ToString, value: impl AsRef<str>) -> Self {
+        self.header(name, encoded_word::encode(value.as_ref()))
+    }
+
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub fn headers(&self) -> &Vec<(String, String)> {
+        &self.headers
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn message_id(&self) -> Option<&str> {
+        self.get("Message-ID")
+            .map(|id| id.trim().trim_start_matches('<').trim_end_matches('>'))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for (name, value) in &self.headers {
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(b": ");
+            bytes.extend_from_slice(value.as_bytes());
+            bytes.extend_from_slice(b"\r\n");
+        }
+
+        bytes.extend_from_slice(b"\r\n");
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+impl From<NewArticle> for ArticlePayload {
+    fn from(article: NewArticle) -> Self {
+        ArticlePayload::new(article.to_bytes())
+    }
+}
+
+impl From<&NewArticle> for ArticlePayload {
+    fn from(article: &NewArticle) -> Self {
+        ArticlePayload::new(article.to_bytes())
+    }
+}
+
+impl From<Vec<u8>> for ArticlePayload {
+    fn from(bytes: Vec<u8>) -> Self {
+        ArticlePayload::new(bytes)
+    }
+}
+
+impl From<&[u8]> for ArticlePayload {
+    fn from(bytes: &[u8]) -> Self {
+        ArticlePayload::new(bytes)
+    }
+}
+
+impl Encode for ArticlePayload {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        let mut lines = self.bytes.split(|b| *b == b'\n').peekable();
+
+        while let Some(line) = lines.next() {
+            // Skip the empty remainder after a trailing line feed
+            if line.is_empty() && lines.peek().is_none() {
+                break;
+            }
+
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+            // Dot-stuffing
+            if line.starts_with(b".") {
+                bytes.put_u8(b'.');
+            }
+
+            bytes.extend_from_slice(line);
+            bytes.extend_from_slice(b"\r\n");
+        }
+
+        write!(bytes.writer(), ".").map_err(Error::encode)
+    }
+}
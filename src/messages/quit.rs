@@ -0,0 +1,56 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::{ConnectionState, Session};
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+#[derive(Default)]
+pub struct QuitRequest;
+
+#[derive(Default)]
+pub struct QuitResponse {
+    text: String,
+}
+
+impl QuitRequest {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl QuitResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Encode for QuitRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "QUIT").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for QuitRequest {
+    type Response = QuitResponse;
+}
+
+impl ExpectedResponseCode for QuitResponse {
+    const CODES: ResponseCodeTuples = &[(205, false, true)];
+}
+
+impl Decode for QuitResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, _: u16) {
+        session.set_state(ConnectionState::Closed);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::head::HeadType;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::Session;
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub struct StatRequest {
+    _type: HeadType,
+}
+
+#[derive(Default)]
+pub struct StatResponse {
+    number: usize,
+    id: String,
+}
+
+impl StatRequest {
+    pub fn new(_type: HeadType) -> Self {
+        Self { _type }
+    }
+}
+
+impl StatResponse {
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Encode for StatRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        let parameter = match &self._type {
+            HeadType::MessageId(i) => format!("<{}>", i),
+            HeadType::MessageNumber(n) => n.to_string(),
+            HeadType::Empty => "".to_string(),
+        };
+
+        write!(bytes.writer(), "STAT {}", parameter).map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for StatRequest {
+    type Response = StatResponse;
+}
+
+impl ExpectedResponseCode for StatResponse {
+    const CODES: ResponseCodeTuples = &[
+        (223, false, true),
+        (412, false, false),
+        (420, false, false),
+        (423, false, false),
+        (430, false, false),
+    ];
+}
+
+impl Decode for StatResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        if code != 223 {
+            return Ok(());
+        }
+
+        self.number = bytes.get()?;
+
+        let id = bytes.get::<String>()?;
+        self.id = id.trim_start_matches('<').trim_end_matches('>').to_string();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        // Selecting by message-id reports number 0 and keeps the current article
+        if code == 223 && self.number > 0 {
+            session.select_article(self.number);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::payload::ArticlePayload;
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use std::io::Write;
+
+pub struct CheckRequest {
+    id: String,
+}
+
+pub struct TakethisRequest {
+    id: String,
+    payload: ArticlePayload,
+}
+
+// Responses to CHECK and TAKETHIS share one type, as they arrive interleaved on a streaming
+// connection and are told apart by their code and message-id
+#[derive(Default)]
+pub struct StreamResponse {
+    id: String,
+    text: String,
+}
+
+impl CheckRequest {
+    pub fn new(id: impl ToString) -> Self {
+        Self { id: id.to_string() }
+    }
+}
+
+impl TakethisRequest {
+    pub fn new(id: impl ToString, article: impl Into<ArticlePayload>) -> Self {
+        Self {
+            id: id.to_string(),
+            payload: article.into(),
+        }
+    }
+}
+
+impl StreamResponse {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Encode for CheckRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "CHECK <{}>", self.id).map_err(Error::encode)
+    }
+}
+
+impl Encode for TakethisRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "TAKETHIS <{}>\r\n", self.id).map_err(Error::encode)?;
+
+        self.payload.encoder(bytes)
+    }
+}
+
+impl ExpectedResponse for CheckRequest {
+    type Response = StreamResponse;
+}
+
+impl ExpectedResponse for TakethisRequest {
+    type Response = StreamResponse;
+}
+
+impl ExpectedResponseCode for StreamResponse {
+    const CODES: ResponseCodeTuples = &[
+        (238, false, true),
+        (239, false, true),
+        (431, false, false),
+        (438, false, false),
+        (439, false, false),
+    ];
+}
+
+impl Decode for StreamResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let id = bytes.get::<String>()?;
+
+        self.id = id.trim_start_matches('<').trim_end_matches('>').to_string();
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+}
@@ -1,138 +1,427 @@
-use crate::decoder::decoder::{Encode, ExpectedResponse};
-use crate::decoder::ExpectedResponseCode;
-use crate::messages::{Decode, Decoder, ResponseCodeTuples};
-use crate::{Error, Result};
-use bytes::{BufMut, BytesMut};
-use chrono::{DateTime, NaiveDateTime, Utc};
-use std::io::Write;
-
-pub enum RangeType {
-    Single(usize),
-    Start(usize),
-    StartEnd(usize, usize),
-}
-
-pub struct XoverRequest {
-    _type: RangeType,
-}
-
-#[derive(Debug)]
-pub struct XoverMessage {
-    number: usize,
-    subject: String,
-    author: String,
-    date: chrono::DateTime<Utc>,
-}
-
-#[derive(Default)]
-pub struct XoverResponse {
-    messages: Vec<XoverMessage>,
-}
-
-impl XoverRequest {
-    pub fn new(_type: RangeType) -> Self {
-        Self { _type }
-    }
-}
-
-impl XoverMessage {
-    fn new(number: usize, subject: String, author: String, date: chrono::DateTime<Utc>) -> Self {
-        Self {
-            number,
-            subject,
-            author,
-            date,
-        }
-    }
-
-    pub fn number(&self) -> usize {
-        self.number
-    }
-
-    pub fn subject(&self) -> &str {
-        &self.subject
-    }
-
-    pub fn author(&self) -> &str {
-        &self.author
-    }
-
-    pub fn date(&self) -> chrono::DateTime<Utc> {
-        self.date
-    }
-}
-
-impl XoverResponse {
-    pub fn messages(&self) -> &Vec<XoverMessage> {
-        &self.messages
-    }
-}
-
-impl Encode for XoverRequest {
-    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
-        let parameter = match self._type {
-            RangeType::Single(start) => format!("{}", start),
-            RangeType::Start(start) => format!("{}-", start),
-            RangeType::StartEnd(start, end) => format!("{}-{}", start, end),
-        };
-
-        write!(bytes.writer(), "XOVER {}", parameter).map_err(Error::encode)
-    }
-}
-
-impl ExpectedResponse for XoverRequest {
-    type Response = XoverResponse;
-}
-
-impl ExpectedResponseCode for XoverResponse {
-    const CODES: ResponseCodeTuples = &[(224, true, true)];
-}
-
-impl Decode for XoverResponse {
-    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
-    where
-        Self: Sized,
-    {
-        let _ = bytes.line();
-
-        while let Some(mut line) = bytes.line() {
-            let number = line.get_with_delimiter(&[b'\t'])?;
-            let subject = line.get_with_delimiter(&[b'\t'])?;
-            let author = line.get_with_delimiter(&[b'\t'])?;
-            let time = line.get_with_delimiter::<String>(&[b'\t'])?;
-
-            let Some(dt) = parse_datetime(&time) else {
-                continue;
-            };
-
-            self.messages
-                .push(XoverMessage::new(number, subject, author, dt));
-        }
-        Ok(())
-    }
-}
-
-fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
-    if let Ok(dt) = DateTime::parse_from_rfc2822(s) {
-        return Some(dt.with_timezone(&Utc));
-    }
-
-    let formats = [
-        "%a, %d %b %y %H:%M:%S UTC",
-        "%a, %d %b %y %H:%M:%S GMT",
-        "%a, %d %b %Y %H:%M:%S UTC",
-        "%a, %d %b %Y %H:%M:%S GMT",
-    ];
-
-    for fmt in formats {
-        if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
-            return Some(dt.with_timezone(&Utc));
-        }
-
-        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
-            return Some(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
-        }
-    }
-
-    None
-}
+use crate::binary::yenc::YencPart;
+use crate::decoder::decoder::{Encode, ExpectedResponse};
+use crate::decoder::ExpectedResponseCode;
+use crate::messages::encoded_word;
+use crate::messages::headers::{parse_references, strip_id, Xref};
+use crate::messages::{Decode, Decoder, ResponseCodeTuples};
+use crate::session::{OverviewFormat, Session};
+use crate::{Error, Result};
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::{Decompress, FlushDecompress, Status};
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+// Status line marker of an overview compressed by XFEATURE COMPRESS GZIP
+const GZIP_MARKER: &[u8] = b"[COMPRESS=GZIP]";
+
+#[derive(Debug, Clone, Copy)]
+pub enum RangeType {
+    Single(usize),
+    Start(usize),
+    StartEnd(usize, usize),
+}
+
+pub struct XoverRequest {
+    _type: RangeType,
+}
+
+// XZVER: overview data deflated and yEnc encoded, answered like XOVER
+pub struct XzverRequest {
+    _type: RangeType,
+}
+
+// XFEATURE COMPRESS GZIP: following XOVER responses are sent as a single deflate stream
+#[derive(Default)]
+pub struct XfeatureRequest;
+
+#[derive(Default)]
+pub struct XfeatureResponse {
+    text: String,
+}
+
+#[derive(Debug)]
+pub struct XoverMessage {
+    number: usize,
+    subject: String,
+    author: String,
+    date: chrono::DateTime<Utc>,
+    id: String,
+    references: Vec<String>,
+    bytes: Option<usize>,
+    lines: Option<usize>,
+    xref: Option<String>,
+    raw: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct XoverResponse {
+    messages: Vec<XoverMessage>,
+    inflater: Option<Inflater>,
+}
+
+// Incremental inflate of a zlib wrapped or raw deflate stream
+#[derive(Default)]
+struct Inflater {
+    decompress: Option<Decompress>,
+    output: Vec<u8>,
+    done: bool,
+}
+
+impl XoverRequest {
+    pub fn new(_type: RangeType) -> Self {
+        Self { _type }
+    }
+}
+
+impl XzverRequest {
+    pub fn new(_type: RangeType) -> Self {
+        Self { _type }
+    }
+}
+
+impl XfeatureRequest {
+    pub fn compress_gzip() -> Self {
+        Self
+    }
+}
+
+impl XfeatureResponse {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl XoverMessage {
+    // Fields in the order of RFC 3977 OVERVIEW.FMT, followed by optional `Xref:` data
+    fn decode(line: Decoder) -> Option<Self> {
+        let raw = line.as_slice().to_vec();
+        let text = line.text(&raw);
+        let mut fields = text.split('\t');
+
+        let number = fields.next()?.trim().parse().ok()?;
+        let subject = fields.next()?.to_string();
+        let author = fields.next()?.to_string();
+        let date = parse_datetime(fields.next()?.trim())?;
+        let id = strip_id(fields.next().unwrap_or_default()).to_string();
+        let references = parse_references(fields.next().unwrap_or_default())
+            .into_iter()
+            .map(|reference| reference.to_string())
+            .collect();
+        let bytes = fields.next().and_then(|bytes| bytes.trim().parse().ok());
+        let lines = fields.next().and_then(|lines| lines.trim().parse().ok());
+
+        let xref = fields.find_map(|field| {
+            let (name, value) = field.split_once(':')?;
+            name.eq_ignore_ascii_case("Xref")
+                .then(|| value.trim().to_string())
+        });
+
+        Some(Self {
+            number,
+            subject,
+            author,
+            date,
+            id,
+            references,
+            bytes,
+            lines,
+            xref,
+            raw,
+        })
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    // Subject with RFC 2047 encoded words decoded
+    pub fn subject_decoded(&self) -> String {
+        encoded_word::decode(&self.subject)
+    }
+
+    pub fn author_decoded(&self) -> String {
+        encoded_word::decode(&self.author)
+    }
+
+    pub fn date(&self) -> chrono::DateTime<Utc> {
+        self.date
+    }
+
+    // Message-id without angle brackets, empty if the server did not send one
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // Message-ids of the References field, oldest first
+    pub fn references(&self) -> &[String] {
+        &self.references
+    }
+
+    pub fn bytes(&self) -> Option<usize> {
+        self.bytes
+    }
+
+    pub fn lines(&self) -> Option<usize> {
+        self.lines
+    }
+
+    pub fn xref(&self) -> Option<Xref> {
+        self.xref.as_deref().and_then(Xref::parse)
+    }
+
+    // Overview line as received, before any charset decoding
+    pub fn raw(&self) -> &[u8] {
+        &self.raw
+    }
+}
+
+impl XoverResponse {
+    pub fn messages(&self) -> &Vec<XoverMessage> {
+        &self.messages
+    }
+
+    pub fn into_messages(self) -> Vec<XoverMessage> {
+        self.messages
+    }
+}
+
+impl XoverResponse {
+    fn parse_lines(&mut self, bytes: &mut Decoder) -> Result<()> {
+        while let Some(line) = bytes.line() {
+            // Terminator of an inflated overview
+            if line.as_slice() == b"." {
+                break;
+            }
+
+            // Malformed lines are skipped, they must not fail the whole range
+            if let Some(message) = XoverMessage::decode(line) {
+                self.messages.push(message);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Inflater {
+    // Feeds all compressed bytes received so far, returns their length once the stream ended
+    fn feed(&mut self, input: &[u8]) -> Result<Option<usize>> {
+        if self.decompress.is_none() {
+            if input.len() < 2 {
+                return Ok(None);
+            }
+
+            // zlib header: deflate method and a header checksum divisible by 31
+            let zlib =
+                input[0] & 0x0f == 8 && u16::from_be_bytes([input[0], input[1]]).is_multiple_of(31);
+            self.decompress = Some(Decompress::new(zlib));
+        }
+
+        let Some(decompress) = self.decompress.as_mut() else {
+            return Ok(None);
+        };
+
+        while !self.done {
+            let (read, written) = (decompress.total_in(), decompress.total_out());
+            self.output.reserve(input.len().max(4096));
+
+            let status = decompress
+                .decompress_vec(
+                    &input[read as usize..],
+                    &mut self.output,
+                    FlushDecompress::None,
+                )
+                .map_err(Error::decode)?;
+
+            self.done = status == Status::StreamEnd;
+
+            if !self.done && decompress.total_in() == read && decompress.total_out() == written {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(decompress.total_in() as usize))
+    }
+}
+
+impl Encode for XoverRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "XOVER {}", self._type).map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for XoverRequest {
+    type Response = XoverResponse;
+}
+
+impl Encode for XzverRequest {
+    const READER_COMMAND: bool = true;
+
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "XZVER {}", self._type).map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for XzverRequest {
+    type Response = XoverResponse;
+}
+
+impl ExpectedResponseCode for XoverResponse {
+    const CODES: ResponseCodeTuples = &[
+        (224, true, true),
+        (412, false, false),
+        (420, false, false),
+        (423, false, false),
+        (500, false, false),
+        (501, false, false),
+    ];
+}
+
+impl Decode for XoverResponse {
+    fn decode(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let Some(inflater) = self.inflater.take() else {
+            bytes.terminate(Self::CODES, code)?;
+            return self.decoder(bytes, code);
+        };
+
+        // Continue with the status line followed by the inflated overview
+        let status = bytes.line_end().unwrap_or_default() + 2;
+        let mut plain = BytesMut::from(&bytes[..status]);
+        plain.extend_from_slice(&inflater.output);
+
+        let mut plain = bytes.derive(plain);
+        plain.terminate(Self::CODES, code)?;
+
+        self.decoder(&mut plain, code)
+    }
+
+    fn decoder(&mut self, bytes: &mut Decoder, code: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let _ = bytes.line();
+
+        if code != 224 || !bytes.starts_with(b"=ybegin ") {
+            return self.parse_lines(bytes);
+        }
+
+        // XZVER overview
+        let part = YencPart::decode(bytes.as_slice())?;
+        let mut inflater = Inflater::default();
+
+        if inflater.feed(part.data())?.is_none() {
+            return Err(Error::BinaryError("Truncated compressed overview"));
+        }
+
+        self.parse_lines(&mut bytes.derive(BytesMut::from(&inflater.output[..])))
+    }
+
+    fn frame(&mut self, bytes: &Decoder) -> Result<Option<usize>>
+    where
+        Self: Sized,
+    {
+        let Some(end) = bytes.line_end() else {
+            return Ok(None);
+        };
+
+        let status = &bytes[..end];
+
+        if !status.starts_with(b"224")
+            || !status
+                .windows(GZIP_MARKER.len())
+                .any(|window| window == GZIP_MARKER)
+        {
+            return bytes.frame(Self::CODES);
+        }
+
+        // The compressed stream is not terminated, it ends where the deflate stream ends
+        let start = end + 2;
+
+        Ok(self
+            .inflater
+            .get_or_insert_default()
+            .feed(&bytes[start..])?
+            .map(|len| start + len))
+    }
+}
+
+impl Encode for XfeatureRequest {
+    fn encoder(&self, bytes: &mut BytesMut) -> Result<()> {
+        write!(bytes.writer(), "XFEATURE COMPRESS GZIP").map_err(Error::encode)
+    }
+}
+
+impl ExpectedResponse for XfeatureRequest {
+    type Response = XfeatureResponse;
+}
+
+impl ExpectedResponseCode for XfeatureResponse {
+    const CODES: ResponseCodeTuples =
+        &[(290, false, true), (500, false, false), (501, false, false)];
+}
+
+impl Decode for XfeatureResponse {
+    fn decoder(&mut self, bytes: &mut Decoder, _: u16) -> Result<()>
+    where
+        Self: Sized,
+    {
+        self.text = bytes.get_line()?.unwrap_or_default();
+
+        Ok(())
+    }
+
+    fn apply(&self, session: &mut Session, code: u16) {
+        if code == 290 {
+            session.set_overview_format(OverviewFormat::Gzip);
+        }
+    }
+}
+
+impl Display for RangeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeType::Single(start) => write!(f, "{}", start),
+            RangeType::Start(start) => write!(f, "{}-", start),
+            RangeType::StartEnd(start, end) => write!(f, "{}-{}", start, end),
+        }
+    }
+}
+
+pub(crate) fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc2822(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+
+    let formats = [
+        "%a, %d %b %y %H:%M:%S UTC",
+        "%a, %d %b %y %H:%M:%S GMT",
+        "%a, %d %b %Y %H:%M:%S UTC",
+        "%a, %d %b %Y %H:%M:%S GMT",
+    ];
+
+    for fmt in formats {
+        if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+            return Some(dt.with_timezone(&Utc));
+        }
+
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Some(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
+        }
+    }
+
+    None
+}
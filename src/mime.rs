@@ -0,0 +1,448 @@
+use crate::decoder::Decoder;
+use crate::messages::encoded_word;
+use crate::messages::headers::Headers;
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use bytes::BytesMut;
+use encoding_rs::Encoding;
+
+// Lenient base64 for bodies, line breaks and padding are removed before decoding
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_padding_mode(DecodePaddingMode::RequireNone)
+        .with_decode_allow_trailing_bits(true),
+);
+
+// Content-Type header value, the MIME type is lowercase
+#[derive(Debug, Clone)]
+pub struct ContentType {
+    mime_type: String,
+    parameters: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferEncoding {
+    SevenBit,
+    EightBit,
+    Binary,
+    Base64,
+    QuotedPrintable,
+    Other(String),
+}
+
+// A leaf of the MIME tree of an article with its decoded content
+#[derive(Debug, Clone)]
+pub struct MimePart {
+    headers: Headers,
+    content_type: ContentType,
+    transfer_encoding: TransferEncoding,
+    data: Vec<u8>,
+}
+
+// Splits an article body into its MIME parts. Nested multiparts are flattened, bodies that are
+// not multipart yield a single part. Parts without a Content-Type are `text/plain`.
+pub fn parse(headers: &Headers, body: &[u8]) -> Vec<MimePart> {
+    let mut parts = Vec::new();
+    collect(headers.clone(), body, &mut parts);
+    parts
+}
+
+// Parts of a multipart body with a known boundary
+pub(crate) fn parse_multipart(body: &[u8], boundary: &str) -> Vec<MimePart> {
+    let mut parts = Vec::new();
+    collect_multipart(body, boundary, &mut parts);
+    parts
+}
+
+fn collect(headers: Headers, body: &[u8], parts: &mut Vec<MimePart>) {
+    let content_type = headers.content_type().unwrap_or_default();
+
+    if let Some(boundary) = content_type
+        .boundary()
+        .filter(|_| content_type.is_multipart())
+    {
+        collect_multipart(body, boundary, parts);
+        return;
+    }
+
+    let transfer_encoding = headers.transfer_encoding();
+
+    let data = match transfer_encoding {
+        TransferEncoding::Base64 => decode_base64(body),
+        TransferEncoding::QuotedPrintable => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    };
+
+    parts.push(MimePart {
+        headers,
+        content_type,
+        transfer_encoding,
+        data,
+    });
+}
+
+fn collect_multipart(body: &[u8], boundary: &str, parts: &mut Vec<MimePart>) {
+    for part in split_multipart(body, boundary) {
+        let mut decoder = Decoder::with_bytes(BytesMut::from(part));
+        let headers = Headers::decode(&mut decoder).unwrap_or_default();
+
+        collect(headers, decoder.as_slice(), parts);
+    }
+}
+
+// Bodies between the boundary delimiter lines. The line break before a delimiter belongs to
+// the delimiter, the preamble and the epilogue are dropped.
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+    let mut start = None;
+    let mut position = 0;
+
+    for line in body.split_inclusive(|b| *b == b'\n') {
+        let rest = line
+            .trim_ascii_end()
+            .strip_prefix(delimiter.as_bytes())
+            .filter(|rest| rest.is_empty() || *rest == b"--");
+
+        if let Some(rest) = rest {
+            if let Some(start) = start {
+                parts.push(strip_line_break(&body[start..position]));
+            }
+
+            if rest == b"--" {
+                return parts;
+            }
+
+            start = Some(position + line.len());
+        }
+
+        position += line.len();
+    }
+
+    // Tolerate a missing close delimiter
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+
+    parts
+}
+
+fn strip_line_break(part: &[u8]) -> &[u8] {
+    let part = part.strip_suffix(b"\n").unwrap_or(part);
+    part.strip_suffix(b"\r").unwrap_or(part)
+}
+
+pub fn decode_base64(data: &[u8]) -> Vec<u8> {
+    let mut encoded = data
+        .iter()
+        .copied()
+        .take_while(|b| *b != b'=')
+        .filter(|b| b.is_ascii_alphanumeric() || *b == b'+' || *b == b'/')
+        .collect::<Vec<_>>();
+
+    // A single dangling character carries no complete byte
+    if encoded.len() % 4 == 1 {
+        encoded.pop();
+    }
+
+    BASE64.decode(encoded).unwrap_or_default()
+}
+
+// Invalid escapes are kept as they are
+pub fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(data.len());
+
+    for line in data.split_inclusive(|b| *b == b'\n') {
+        let content = line.trim_ascii_end();
+        let mut bytes = content.iter().enumerate();
+
+        while let Some((i, b)) = bytes.next() {
+            if *b != b'=' {
+                decoded.push(*b);
+                continue;
+            }
+
+            // Soft line break
+            if i + 1 == content.len() {
+                break;
+            }
+
+            match content
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(str::from_utf8(hex).ok()?, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    bytes.nth(1);
+                }
+                None => decoded.push(b'='),
+            }
+        }
+
+        if line.ends_with(b"\n") && !content.ends_with(b"=") {
+            decoded.extend_from_slice(b"\r\n");
+        }
+    }
+
+    decoded
+}
+
+impl ContentType {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut fields = split_parameters(value).into_iter();
+        let mime_type = fields.next()?.trim().to_ascii_lowercase();
+
+        if !mime_type.contains('/') {
+            return None;
+        }
+
+        Some(Self {
+            mime_type,
+            parameters: parse_parameters(fields),
+        })
+    }
+
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    pub fn is_multipart(&self) -> bool {
+        self.mime_type.starts_with("multipart/")
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.mime_type.starts_with("text/")
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<String> {
+        parameter(&self.parameters, name)
+    }
+
+    pub fn boundary(&self) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("boundary"))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn charset(&self) -> Option<String> {
+        self.parameter("charset")
+    }
+}
+
+impl Default for ContentType {
+    fn default() -> Self {
+        Self {
+            mime_type: "text/plain".to_string(),
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl TransferEncoding {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "7bit" => TransferEncoding::SevenBit,
+            "8bit" => TransferEncoding::EightBit,
+            "binary" => TransferEncoding::Binary,
+            "base64" => TransferEncoding::Base64,
+            "quoted-printable" => TransferEncoding::QuotedPrintable,
+            other => TransferEncoding::Other(other.to_string()),
+        }
+    }
+}
+
+impl MimePart {
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    pub fn content_type(&self) -> &ContentType {
+        &self.content_type
+    }
+
+    pub fn transfer_encoding(&self) -> &TransferEncoding {
+        &self.transfer_encoding
+    }
+
+    // Content with the transfer encoding removed
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    // Content decoded with the charset of the part, `None` for non-text parts
+    pub fn text(&self) -> Option<String> {
+        if !self.content_type.is_text() {
+            return None;
+        }
+
+        let charset = self
+            .content_type
+            .charset()
+            .and_then(|charset| Encoding::for_label(charset.as_bytes()))
+            .unwrap_or(encoding_rs::UTF_8);
+
+        Some(
+            charset
+                .decode_without_bom_handling(&self.data)
+                .0
+                .into_owned(),
+        )
+    }
+
+    // File name from Content-Disposition, or the `name` of the Content-Type
+    pub fn filename(&self) -> Option<String> {
+        self.disposition()
+            .and_then(|(_, parameters)| parameter(&parameters, "filename"))
+            .or_else(|| self.content_type.parameter("name"))
+            .map(|name| encoded_word::decode(&name))
+    }
+
+    pub fn is_attachment(&self) -> bool {
+        self.disposition()
+            .is_some_and(|(disposition, _)| disposition == "attachment")
+            || self.filename().is_some()
+    }
+
+    fn disposition(&self) -> Option<(String, Vec<(String, String)>)> {
+        let value = self.headers.get("Content-Disposition")?;
+        let mut fields = split_parameters(value).into_iter();
+        let disposition = fields.next()?.trim().to_ascii_lowercase();
+
+        Some((disposition, parse_parameters(fields)))
+    }
+}
+
+// Splits at semicolons outside of quoted strings
+fn split_parameters(value: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                fields.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    fields.push(&value[start..]);
+    fields
+}
+
+fn parse_parameters<'a>(fields: impl Iterator<Item = &'a str>) -> Vec<(String, String)> {
+    fields
+        .filter_map(|field| {
+            let (name, value) = field.split_once('=')?;
+            let value = value.trim();
+
+            let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(quoted) => quoted.replace("\\\"", "\"").replace("\\\\", "\\"),
+                None => value.to_string(),
+            };
+
+            Some((name.trim().to_ascii_lowercase(), value))
+        })
+        .collect()
+}
+
+// Looks up a parameter, joining RFC 2231 continuations (`name*0`, `name*1*`, ...) and decoding
+// extended values (`name*=charset''value`)
+fn parameter(parameters: &[(String, String)], name: &str) -> Option<String> {
+    let name = name.to_ascii_lowercase();
+
+    if let Some((_, value)) = parameters.iter().find(|(n, _)| *n == name) {
+        return Some(value.clone());
+    }
+
+    let mut segments = parameters
+        .iter()
+        .filter_map(|(n, value)| {
+            let rest = n.strip_prefix(&name)?.strip_prefix('*')?;
+            let (index, extended) = match rest.strip_suffix('*') {
+                Some(index) => (index, true),
+                None => (rest, rest.is_empty()),
+            };
+            let index = match index {
+                "" => 0,
+                index => index.parse::<usize>().ok()?,
+            };
+
+            Some((index, extended, value.as_str()))
+        })
+        .collect::<Vec<_>>();
+
+    if segments.is_empty() {
+        return None;
+    }
+
+    segments.sort_by_key(|(index, _, _)| *index);
+
+    let mut charset = encoding_rs::UTF_8;
+    let mut bytes = Vec::new();
+
+    for (index, extended, value) in segments {
+        if !extended {
+            bytes.extend_from_slice(value.as_bytes());
+            continue;
+        }
+
+        // The first extended segment names the charset and language
+        let value = match (index, value.splitn(3, '\'').collect::<Vec<_>>().as_slice()) {
+            (0, [label, _, value]) => {
+                charset = Encoding::for_label(label.as_bytes()).unwrap_or(charset);
+                *value
+            }
+            _ => value,
+        };
+
+        bytes.extend(percent_decode(value));
+    }
+
+    Some(charset.decode_without_bom_handling(&bytes).0.into_owned())
+}
+
+fn percent_decode(value: &str) -> Vec<u8> {
+    let value = value.as_bytes();
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut i = 0;
+
+    while i < value.len() {
+        // Invalid escapes are kept as they are
+        let decoded = match value[i] {
+            b'%' => value
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match decoded {
+            Some(b) => {
+                bytes.push(b);
+                i += 3;
+            }
+            None => {
+                bytes.push(value[i]);
+                i += 1;
+            }
+        }
+    }
+
+    bytes
+}
@@ -0,0 +1,390 @@
+use crate::messages::group::GroupResponse;
+use crate::messages::listgroup::{ListgroupRequest, ListgroupResponse};
+use crate::messages::xover::RangeType;
+use crate::Error;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// A set of article numbers kept as sorted, disjoint and non-adjacent inclusive ranges, written
+// in the `.newsrc` notation `1-5,7,9-12`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleSet {
+    ranges: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewsrcGroup {
+    name: String,
+    subscribed: bool,
+    read: ArticleSet,
+}
+
+// The `.newsrc` format shared by rn, tin, slrn and friends: one line per group, `:` for
+// subscribed and `!` for unsubscribed groups, followed by the read articles. The `options`
+// line of rn is kept as it is.
+#[derive(Debug, Clone, Default)]
+pub struct Newsrc {
+    options: Option<String>,
+    groups: Vec<NewsrcGroup>,
+    index: HashMap<String, usize>,
+}
+
+impl ArticleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ranges(&self) -> &[(usize, usize)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Number of articles in the set
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|(low, high)| high - low + 1).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(|(low, high)| *low..=*high)
+    }
+
+    pub fn contains(&self, number: usize) -> bool {
+        let position = self.ranges.partition_point(|(_, high)| *high < number);
+
+        self.ranges
+            .get(position)
+            .is_some_and(|(low, _)| *low <= number)
+    }
+
+    pub fn insert(&mut self, number: usize) {
+        self.insert_range(number, number);
+    }
+
+    pub fn insert_range(&mut self, low: usize, high: usize) {
+        if low > high {
+            return;
+        }
+
+        // Ranges overlapping or adjacent to the new one are merged into it
+        let start = self
+            .ranges
+            .partition_point(|(_, end)| end.saturating_add(1) < low);
+        let stop = self
+            .ranges
+            .partition_point(|(begin, _)| *begin <= high.saturating_add(1));
+
+        let merged = match self.ranges[start..stop] {
+            [] => (low, high),
+            [(first, _), .., (_, last)] | [(first, last)] => (first.min(low), last.max(high)),
+        };
+
+        self.ranges.splice(start..stop, [merged]);
+    }
+
+    pub fn remove(&mut self, number: usize) {
+        self.remove_range(number, number);
+    }
+
+    pub fn remove_range(&mut self, low: usize, high: usize) {
+        if low > high {
+            return;
+        }
+
+        self.ranges = self
+            .ranges
+            .iter()
+            .flat_map(|&(begin, end)| {
+                if end < low || begin > high {
+                    return vec![(begin, end)];
+                }
+
+                let before = (begin < low).then(|| (begin, low - 1));
+                let after = (end > high).then(|| (high + 1, end));
+                before.into_iter().chain(after).collect()
+            })
+            .collect();
+    }
+
+    pub fn union(&self, other: &ArticleSet) -> ArticleSet {
+        let mut union = self.clone();
+        for (low, high) in &other.ranges {
+            union.insert_range(*low, *high);
+        }
+        union
+    }
+
+    pub fn difference(&self, other: &ArticleSet) -> ArticleSet {
+        let mut difference = self.clone();
+        for (low, high) in &other.ranges {
+            difference.remove_range(*low, *high);
+        }
+        difference
+    }
+
+    pub fn intersection(&self, other: &ArticleSet) -> ArticleSet {
+        self.difference(&self.difference(other))
+    }
+
+    // Number of articles between `low` and `high` that are not in the set
+    pub fn missing_in(&self, low: usize, high: usize) -> usize {
+        if low > high {
+            return 0;
+        }
+
+        let present = self
+            .ranges
+            .iter()
+            .filter(|(begin, end)| *end >= low && *begin <= high)
+            .map(|(begin, end)| (*end).min(high) - (*begin).max(low) + 1)
+            .sum::<usize>();
+
+        high - low + 1 - present
+    }
+}
+
+impl FromIterator<usize> for ArticleSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = ArticleSet::new();
+        iter.into_iter().for_each(|number| set.insert(number));
+        set
+    }
+}
+
+impl FromStr for ArticleSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut set = ArticleSet::new();
+
+        for range in s.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            let (low, high) = range.split_once('-').unwrap_or((range, range));
+            let low = low.trim().parse().map_err(|_| Error::DecodeFromStr)?;
+            let high = high.trim().parse().map_err(|_| Error::DecodeFromStr)?;
+
+            // Some readers write `1-0` for a group without read articles
+            set.insert_range(low, high);
+        }
+
+        Ok(set)
+    }
+}
+
+impl Display for ArticleSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (low, high)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+
+            match low == high {
+                true => write!(f, "{}", low)?,
+                false => write!(f, "{}-{}", low, high)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NewsrcGroup {
+    pub fn new(name: impl ToString, subscribed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            subscribed,
+            read: ArticleSet::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn subscribed(&self) -> bool {
+        self.subscribed
+    }
+
+    pub fn set_subscribed(&mut self, subscribed: bool) {
+        self.subscribed = subscribed;
+    }
+
+    pub fn read(&self) -> &ArticleSet {
+        &self.read
+    }
+
+    pub fn read_mut(&mut self) -> &mut ArticleSet {
+        &mut self.read
+    }
+
+    pub fn is_read(&self, number: usize) -> bool {
+        self.read.contains(number)
+    }
+
+    pub fn mark_read(&mut self, number: usize) {
+        self.read.insert(number);
+    }
+
+    pub fn mark_unread(&mut self, number: usize) {
+        self.read.remove(number);
+    }
+
+    // Marks everything up to the high-water mark of the group as read
+    pub fn catch_up(&mut self, group: &GroupResponse) {
+        self.read.insert_range(1, group.high());
+    }
+
+    // Estimated unread count from GROUP, as article numbers may have gaps the estimate is
+    // capped by the article count the server reported
+    pub fn unread_count(&self, group: &GroupResponse) -> usize {
+        if group.number() == 0 || group.low() > group.high() {
+            return 0;
+        }
+
+        self.read
+            .missing_in(group.low(), group.high())
+            .min(group.number())
+    }
+
+    // Exact unread articles from a LISTGROUP response
+    pub fn unread(&self, listgroup: &ListgroupResponse) -> Vec<usize> {
+        listgroup
+            .articles()
+            .iter()
+            .copied()
+            .filter(|number| !self.read.contains(*number))
+            .collect()
+    }
+
+    // LISTGROUP request for the articles from the first unread one on
+    pub fn unread_request(&self) -> ListgroupRequest {
+        let first = match self.read.ranges.first() {
+            Some((1, high)) => high + 1,
+            _ => 1,
+        };
+
+        ListgroupRequest::new(&self.name, Some(RangeType::Start(first)))
+    }
+}
+
+impl Newsrc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Arguments of the `options` line
+    pub fn options(&self) -> Option<&str> {
+        self.options.as_deref()
+    }
+
+    pub fn set_options(&mut self, options: Option<String>) {
+        self.options = options;
+    }
+
+    pub fn groups(&self) -> &[NewsrcGroup] {
+        &self.groups
+    }
+
+    pub fn subscribed(&self) -> impl Iterator<Item = &NewsrcGroup> {
+        self.groups.iter().filter(|group| group.subscribed)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NewsrcGroup> {
+        self.index.get(name).map(|i| &self.groups[*i])
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NewsrcGroup> {
+        self.index.get(name).map(|i| &mut self.groups[*i])
+    }
+
+    // Returns the group, appending it unsubscribed when it is not yet known
+    pub fn entry(&mut self, name: &str) -> &mut NewsrcGroup {
+        let index = match self.index.get(name) {
+            Some(index) => *index,
+            None => {
+                self.groups.push(NewsrcGroup::new(name, false));
+                self.index.insert(name.to_string(), self.groups.len() - 1);
+                self.groups.len() - 1
+            }
+        };
+
+        &mut self.groups[index]
+    }
+
+    pub fn subscribe(&mut self, name: &str) {
+        self.entry(name).subscribed = true;
+    }
+
+    pub fn unsubscribe(&mut self, name: &str) {
+        self.entry(name).subscribed = false;
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<NewsrcGroup> {
+        let index = self.index.remove(name)?;
+        let group = self.groups.remove(index);
+
+        self.index
+            .values_mut()
+            .filter(|i| **i > index)
+            .for_each(|i| *i -= 1);
+
+        Some(group)
+    }
+}
+
+impl FromStr for Newsrc {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut newsrc = Newsrc::new();
+
+        for line in s.lines().map(str::trim_end) {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(options) = line.strip_prefix("options ") {
+                newsrc.options = Some(options.trim().to_string());
+                continue;
+            }
+
+            let position = line.find([':', '!']).ok_or(Error::DecodeFromStr)?;
+            let name = line[..position].trim();
+
+            if name.is_empty() {
+                return Err(Error::DecodeFromStr);
+            }
+
+            let subscribed = line[position..].starts_with(':');
+            let read = line[position + 1..].parse::<ArticleSet>()?;
+
+            // Lines repeating a group add to it, it stays subscribed if any of them says so
+            let group = newsrc.entry(name);
+            group.subscribed |= subscribed;
+            group.read = group.read.union(&read);
+        }
+
+        Ok(newsrc)
+    }
+}
+
+impl Display for Newsrc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(options) = &self.options {
+            writeln!(f, "options {}", options)?;
+        }
+
+        for group in &self.groups {
+            let mark = if group.subscribed { ':' } else { '!' };
+
+            match group.read.is_empty() {
+                true => writeln!(f, "{}{}", group.name, mark)?,
+                false => writeln!(f, "{}{} {}", group.name, mark, group.read)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{Error, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+// A SASL mechanism as used by AUTHINFO SASL (RFC 4643). Challenges and responses are passed
+// decoded, base64 is handled by the connection.
+pub trait SaslMechanism {
+    fn name(&self) -> &str;
+
+    // Initial response sent along with AUTHINFO SASL, `None` to wait for a first challenge
+    fn initial(&mut self) -> Result<Option<Vec<u8>>>;
+
+    // Response to a 383 challenge
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>>;
+
+    // Called on success with the additional data of a 283 response, empty for 281
+    fn finish(&mut self, _data: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+pub struct Plain {
+    authzid: String,
+    username: String,
+    password: String,
+}
+
+#[derive(Copy, Clone)]
+enum ScramHash {
+    Sha1,
+    Sha256,
+}
+
+enum ScramState {
+    Initial,
+    ClientFirst { nonce: String, bare: String },
+    ClientFinal { signature: Vec<u8> },
+    Done,
+}
+
+// SCRAM (RFC 5802, RFC 7677) without channel binding
+pub struct Scram {
+    hash: ScramHash,
+    username: String,
+    password: String,
+    state: ScramState,
+}
+
+impl Plain {
+    pub fn new(username: impl ToString, password: impl ToString) -> Self {
+        Self {
+            authzid: String::new(),
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    pub fn with_authzid(mut self, authzid: impl ToString) -> Self {
+        self.authzid = authzid.to_string();
+        self
+    }
+}
+
+impl SaslMechanism for Plain {
+    fn name(&self) -> &str {
+        "PLAIN"
+    }
+
+    fn initial(&mut self) -> Result<Option<Vec<u8>>> {
+        Ok(Some(
+            format!("{}\0{}\0{}", self.authzid, self.username, self.password).into_bytes(),
+        ))
+    }
+
+    fn step(&mut self, _: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::SaslError("PLAIN does not expect a challenge"))
+    }
+}
+
+impl Scram {
+    pub fn sha1(username: impl ToString, password: impl ToString) -> Self {
+        Self::new(ScramHash::Sha1, username, password)
+    }
+
+    pub fn sha256(username: impl ToString, password: impl ToString) -> Self {
+        Self::new(ScramHash::Sha256, username, password)
+    }
+
+    fn new(hash: ScramHash, username: impl ToString, password: impl ToString) -> Self {
+        Self {
+            hash,
+            username: username.to_string(),
+            password: password.to_string(),
+            state: ScramState::Initial,
+        }
+    }
+
+    fn client_final(
+        &self,
+        nonce: &str,
+        bare: &str,
+        challenge: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let server_first = str::from_utf8(challenge).map_err(Error::decode)?;
+
+        let server_nonce = scram_attribute(server_first, 'r')?;
+        let salt = STANDARD
+            .decode(scram_attribute(server_first, 's')?)
+            .map_err(Error::decode)?;
+        let iterations = scram_attribute(server_first, 'i')?
+            .parse::<u32>()
+            .map_err(Error::decode)?;
+
+        if !server_nonce.starts_with(nonce) || iterations == 0 {
+            return Err(Error::SaslError("Invalid SCRAM server challenge"));
+        }
+
+        let without_proof = format!("c=biws,r={}", server_nonce);
+        let message = format!("{},{},{}", bare, server_first, without_proof);
+
+        let salted = self.hash.hi(self.password.as_bytes(), &salt, iterations);
+        let client_key = self.hash.hmac(&salted, b"Client Key");
+        let stored_key = self.hash.digest(&client_key);
+        let client_signature = self.hash.hmac(&stored_key, message.as_bytes());
+        let server_key = self.hash.hmac(&salted, b"Server Key");
+        let server_signature = self.hash.hmac(&server_key, message.as_bytes());
+
+        let proof = client_key
+            .iter()
+            .zip(client_signature)
+            .map(|(k, s)| k ^ s)
+            .collect::<Vec<_>>();
+
+        let client_final = format!("{},p={}", without_proof, STANDARD.encode(proof));
+
+        Ok((client_final.into_bytes(), server_signature))
+    }
+
+    fn verify(&mut self, data: &[u8]) -> Result<()> {
+        let ScramState::ClientFinal { signature } = &self.state else {
+            return Err(Error::SaslError("Unexpected SCRAM server message"));
+        };
+
+        let server_final = str::from_utf8(data).map_err(Error::decode)?;
+
+        if let Ok(error) = scram_attribute(server_final, 'e') {
+            return Err(Error::SaslError(match error {
+                "invalid-proof" => "SCRAM proof rejected by server",
+                _ => "SCRAM authentication failed",
+            }));
+        }
+
+        let verifier = STANDARD
+            .decode(scram_attribute(server_final, 'v')?)
+            .map_err(Error::decode)?;
+
+        if verifier != *signature {
+            return Err(Error::SaslError("SCRAM server signature mismatch"));
+        }
+
+        self.state = ScramState::Done;
+
+        Ok(())
+    }
+}
+
+impl SaslMechanism for Scram {
+    fn name(&self) -> &str {
+        match self.hash {
+            ScramHash::Sha1 => "SCRAM-SHA-1",
+            ScramHash::Sha256 => "SCRAM-SHA-256",
+        }
+    }
+
+    fn initial(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut random = [0u8; 18];
+        getrandom::getrandom(&mut random).map_err(|_| Error::SaslError("No randomness"))?;
+
+        let nonce = STANDARD.encode(random);
+        let username = self.username.replace('=', "=3D").replace(',', "=2C");
+        let bare = format!("n={},r={}", username, nonce);
+        let client_first = format!("n,,{}", bare);
+
+        self.state = ScramState::ClientFirst { nonce, bare };
+
+        Ok(Some(client_first.into_bytes()))
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>> {
+        match &self.state {
+            ScramState::ClientFirst { nonce, bare } => {
+                let (client_final, signature) = self.client_final(nonce, bare, challenge)?;
+                self.state = ScramState::ClientFinal { signature };
+
+                Ok(client_final)
+            }
+            // Server final message sent as a challenge, answered with an empty response
+            ScramState::ClientFinal { .. } => self.verify(challenge).map(|_| Vec::new()),
+            _ => Err(Error::SaslError("Unexpected SCRAM server message")),
+        }
+    }
+
+    // The server signature arrives either as last challenge or with the 283 response, success
+    // without it is no proof that the server knows the password
+    fn finish(&mut self, data: &[u8]) -> Result<()> {
+        match self.state {
+            ScramState::Done => Ok(()),
+            ScramState::ClientFinal { .. } if !data.is_empty() => self.verify(data),
+            _ => Err(Error::SaslError("SCRAM server signature missing")),
+        }
+    }
+}
+
+impl ScramHash {
+    fn hmac(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        match self {
+            ScramHash::Sha1 => {
+                let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC takes any key size");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+            ScramHash::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes any key size");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+        }
+    }
+
+    fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ScramHash::Sha1 => Sha1::digest(data).to_vec(),
+            ScramHash::Sha256 => Sha256::digest(data).to_vec(),
+        }
+    }
+
+    // PBKDF2 with HMAC as pseudorandom function and a single output block
+    fn hi(&self, password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+        let mut block = salt.to_vec();
+        block.extend_from_slice(&1u32.to_be_bytes());
+
+        let mut u = self.hmac(password, &block);
+        let mut result = u.clone();
+
+        for _ in 1..iterations {
+            u = self.hmac(password, &u);
+            result.iter_mut().zip(&u).for_each(|(r, u)| *r ^= u);
+        }
+
+        result
+    }
+}
+
+// Looks up a `key=value` attribute of a SCRAM message
+fn scram_attribute(message: &str, key: char) -> Result<&str> {
+    message
+        .split(',')
+        .find_map(|attribute| {
+            attribute
+                .strip_prefix(key)
+                .and_then(|a| a.strip_prefix('='))
+        })
+        .ok_or(Error::SaslError("Missing SCRAM attribute"))
+}
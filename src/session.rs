@@ -0,0 +1,128 @@
+use crate::messages::capabilities::Capabilities;
+use chrono::{DateTime, TimeDelta, Utc};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Unknown,
+    Transit,
+    Reader,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ConnectionState {
+    #[default]
+    Open,
+    Authenticated,
+    // The stream failed or lost sync, the connection cannot be used any longer
+    Broken,
+    Closed,
+}
+
+// Compressed overview extension used by `NewsConnection::overview`
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum OverviewFormat {
+    #[default]
+    Unknown,
+    Xzver,
+    Gzip,
+    Plain,
+}
+
+// Server side state of a connection, as far as it can be derived from the responses seen
+#[derive(Default)]
+pub struct Session {
+    state: ConnectionState,
+    group: Option<String>,
+    article: Option<usize>,
+    capabilities: Option<Capabilities>,
+    mode: Mode,
+    compressed: bool,
+    overview: OverviewFormat,
+    clock_skew: Option<TimeDelta>,
+}
+
+impl Session {
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    pub fn article(&self) -> Option<usize> {
+        self.article
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    // COMPRESS DEFLATE is active, all further traffic is compressed
+    pub fn compressed(&self) -> bool {
+        self.compressed
+    }
+
+    pub fn overview_format(&self) -> OverviewFormat {
+        self.overview
+    }
+
+    // Server clock minus local clock, `None` until measured
+    pub fn clock_skew(&self) -> Option<TimeDelta> {
+        self.clock_skew
+    }
+
+    // Translates a local time into server time using the measured clock skew
+    pub fn server_time(&self, local: DateTime<Utc>) -> DateTime<Utc> {
+        local + self.clock_skew.unwrap_or_default()
+    }
+
+    // Last capabilities received, `None` if never fetched or discarded after a state change
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    pub(crate) fn set_state(&mut self, state: ConnectionState) {
+        self.state = state;
+    }
+
+    pub(crate) fn select_group(&mut self, group: impl ToString, article: Option<usize>) {
+        self.group = Some(group.to_string());
+        self.article = article;
+    }
+
+    pub(crate) fn select_article(&mut self, article: usize) {
+        self.article = Some(article);
+    }
+
+    pub(crate) fn set_capabilities(&mut self, capabilities: Capabilities) {
+        if capabilities.supports_reader() {
+            self.mode = Mode::Reader;
+        } else if capabilities.supports_mode_reader() {
+            self.mode = Mode::Transit;
+        }
+
+        self.capabilities = Some(capabilities);
+    }
+
+    pub(crate) fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    pub(crate) fn set_compressed(&mut self) {
+        self.compressed = true;
+    }
+
+    pub(crate) fn set_overview_format(&mut self, format: OverviewFormat) {
+        self.overview = format;
+    }
+
+    pub(crate) fn set_clock_skew(&mut self, skew: TimeDelta) {
+        self.clock_skew = Some(skew);
+    }
+
+    pub(crate) fn discard_capabilities(&mut self) {
+        self.capabilities = None;
+    }
+}
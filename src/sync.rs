@@ -0,0 +1,159 @@
+use crate::connection::NewsConnection;
+use crate::messages::group::GroupRequest;
+use crate::messages::xover::{RangeType, XoverMessage};
+use crate::{Error, Result};
+use futures_util::stream::{self, Stream};
+use std::collections::{HashMap, VecDeque};
+
+const CHUNK_SIZE: usize = 1000;
+
+// Last seen article number per group
+pub trait HighWaterStore {
+    fn high_water(&self, group: &str) -> Option<usize>;
+
+    fn set_high_water(&mut self, group: &str, high: usize);
+}
+
+impl HighWaterStore for HashMap<String, usize> {
+    fn high_water(&self, group: &str) -> Option<usize> {
+        self.get(group).copied()
+    }
+
+    fn set_high_water(&mut self, group: &str, high: usize) {
+        self.insert(group.to_string(), high);
+    }
+}
+
+// Fetches the overview of the articles a group received since the stored high-water mark, in
+// chunks of at most `CHUNK_SIZE` articles. The mark advances with every message handed out and
+// to the end of a chunk once all of its messages were, so an interrupted sync resumes where it
+// stopped. A low-water mark above the stored mark or a high-water mark below it means the group
+// was renumbered, it is then synced from its start.
+pub struct GroupSync<'a, S> {
+    connection: &'a mut NewsConnection,
+    store: &'a mut S,
+    group: String,
+    next: usize,
+    high: usize,
+    chunk_size: usize,
+    renumbered: bool,
+    messages: VecDeque<XoverMessage>,
+    // End of the chunk whose messages are being handed out
+    chunk_end: Option<usize>,
+}
+
+impl<'a, S> GroupSync<'a, S>
+where
+    S: HighWaterStore,
+{
+    pub async fn new(
+        connection: &'a mut NewsConnection,
+        store: &'a mut S,
+        group: impl ToString,
+    ) -> Result<Self> {
+        let group = group.to_string();
+        let response = connection.request(GroupRequest::new(&group)).await?;
+
+        if !response.ok() {
+            return Err(Error::UnexpectedResponseCode(response.code()));
+        }
+
+        let (low, high) = (response.low(), response.high());
+        let stored = store.high_water(&group);
+
+        let renumbered =
+            stored.is_some_and(|stored| low > stored.saturating_add(1) || high < stored);
+
+        let next = match stored {
+            Some(stored) if !renumbered => (stored + 1).max(low),
+            _ => low,
+        };
+
+        // Forget the old mark so that a renumbered group is only detected once
+        if renumbered {
+            store.set_high_water(&group, next.saturating_sub(1));
+        }
+
+        // Nothing to fetch from an empty group, its high-water mark may be below the low one
+        let high = match response.number() {
+            0 => 0,
+            _ => high,
+        };
+
+        Ok(Self {
+            connection,
+            store,
+            group,
+            next,
+            high,
+            chunk_size: CHUNK_SIZE,
+            renumbered,
+            messages: VecDeque::new(),
+            chunk_end: None,
+        })
+    }
+
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn renumbered(&self) -> bool {
+        self.renumbered
+    }
+
+    // Article numbers that remain to be fetched
+    pub fn remaining(&self) -> Option<(usize, usize)> {
+        (self.next <= self.high).then_some((self.next, self.high))
+    }
+
+    pub fn connection(&mut self) -> &mut NewsConnection {
+        self.connection
+    }
+
+    // The new messages in article order
+    pub fn into_stream(self) -> impl Stream<Item = Result<XoverMessage>> + 'a {
+        stream::try_unfold(self, |mut sync| async move {
+            Ok(sync.next().await?.map(|message| (message, sync)))
+        })
+    }
+
+    async fn next(&mut self) -> Result<Option<XoverMessage>> {
+        loop {
+            if let Some(message) = self.messages.pop_front() {
+                self.store.set_high_water(&self.group, message.number());
+                return Ok(Some(message));
+            }
+
+            // Articles of a handed out chunk that had no overview are done as well
+            if let Some(end) = self.chunk_end.take() {
+                self.store.set_high_water(&self.group, end);
+            }
+
+            if self.next > self.high {
+                return Ok(None);
+            }
+
+            let end = self.next.saturating_add(self.chunk_size - 1).min(self.high);
+
+            let response = self
+                .connection
+                .overview(RangeType::StartEnd(self.next, end))
+                .await?;
+
+            // A chunk where every article expired or was cancelled is not an error
+            match response.code() {
+                224 => self.messages.extend(response.into_inner().into_messages()),
+                420 | 423 => {}
+                code => return Err(Error::UnexpectedResponseCode(code)),
+            }
+
+            self.chunk_end = Some(end);
+            self.next = end + 1;
+        }
+    }
+}
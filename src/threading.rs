@@ -0,0 +1,322 @@
+use crate::messages::xover::XoverMessage;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+// A node of a thread. Dummy nodes stand in for parents that are referenced but were not part
+// of the overview, or group threads that only share a subject.
+#[derive(Debug, Clone)]
+pub struct ThreadNode<'a> {
+    message: Option<&'a XoverMessage>,
+    children: Vec<ThreadNode<'a>>,
+}
+
+#[derive(Debug, Default)]
+struct Container {
+    message: Option<usize>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+struct Threader<'a> {
+    messages: &'a [XoverMessage],
+    containers: Vec<Container>,
+    ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> ThreadNode<'a> {
+    pub fn message(&self) -> Option<&'a XoverMessage> {
+        self.message
+    }
+
+    pub fn is_dummy(&self) -> bool {
+        self.message.is_none()
+    }
+
+    pub fn children(&self) -> &[ThreadNode<'a>] {
+        &self.children
+    }
+
+    // Earliest date of the node and its descendants
+    pub fn date(&self) -> Option<DateTime<Utc>> {
+        self.message
+            .map(|message| message.date())
+            .into_iter()
+            .chain(self.children.iter().filter_map(|child| child.date()))
+            .min()
+    }
+
+    // Number of messages in the thread, dummies excluded
+    pub fn len(&self) -> usize {
+        self.message.iter().count() + self.children.iter().map(|c| c.len()).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Threads overview data with the JWZ algorithm (https://www.jwz.org/doc/threading.html). Threads
+// and replies are sorted by date.
+pub fn thread(messages: &[XoverMessage]) -> Vec<ThreadNode<'_>> {
+    let mut threader = Threader {
+        messages,
+        containers: Vec::new(),
+        ids: HashMap::new(),
+    };
+
+    for index in 0..messages.len() {
+        threader.add(index);
+    }
+
+    let roots = (0..threader.containers.len())
+        .filter(|c| threader.containers[*c].parent.is_none())
+        .collect::<Vec<_>>();
+
+    let roots = threader.prune(roots, true);
+    let roots = threader.group_by_subject(roots);
+
+    let mut forest = roots
+        .into_iter()
+        .map(|root| threader.node(root))
+        .collect::<Vec<_>>();
+
+    sort(&mut forest);
+    forest
+}
+
+impl<'a> Threader<'a> {
+    fn container(&mut self, id: &'a str) -> usize {
+        if let Some(container) = self.ids.get(id) {
+            return *container;
+        }
+
+        let container = self.create();
+        self.ids.insert(id, container);
+        container
+    }
+
+    fn create(&mut self) -> usize {
+        self.containers.push(Container::default());
+        self.containers.len() - 1
+    }
+
+    fn add(&mut self, index: usize) {
+        let message = &self.messages[index];
+
+        // Messages without or with a duplicate id get a container of their own
+        let this = match message.id() {
+            "" => self.create(),
+            id => match self.container(id) {
+                c if self.containers[c].message.is_some() => self.create(),
+                c => c,
+            },
+        };
+
+        self.containers[this].message = Some(index);
+
+        // Link the references in order, each one being the parent of the next
+        let mut previous = None;
+        for reference in message.references() {
+            let container = self.container(reference);
+
+            if let Some(parent) = previous
+                && self.containers[container].parent.is_none()
+                && !self.reachable(container, parent)
+            {
+                self.link(parent, container);
+            }
+
+            previous = Some(container);
+        }
+
+        // The last reference is the real parent, replacing what an earlier message implied
+        if let Some(parent) = self.containers[this].parent {
+            self.containers[parent].children.retain(|c| *c != this);
+            self.containers[this].parent = None;
+        }
+
+        if let Some(parent) = previous
+            && !self.reachable(this, parent)
+        {
+            self.link(parent, this);
+        }
+    }
+
+    fn link(&mut self, parent: usize, child: usize) {
+        self.containers[child].parent = Some(parent);
+        self.containers[parent].children.push(child);
+    }
+
+    // Whether `to` is `from` or one of its descendants
+    fn reachable(&self, from: usize, to: usize) -> bool {
+        let mut current = Some(to);
+
+        while let Some(container) = current {
+            if container == from {
+                return true;
+            }
+            current = self.containers[container].parent;
+        }
+
+        false
+    }
+
+    // Drops dummies without children and promotes the children of the others, except for dummy
+    // roots with several children which hold the siblings together
+    fn prune(&mut self, containers: Vec<usize>, root: bool) -> Vec<usize> {
+        let mut pruned = Vec::new();
+
+        for container in containers {
+            let children = std::mem::take(&mut self.containers[container].children);
+            let children = self.prune(children, false);
+
+            match self.containers[container].message {
+                None if children.is_empty() => {}
+                None if !root || children.len() == 1 => pruned.extend(children),
+                _ => {
+                    self.containers[container].children = children;
+                    pruned.push(container);
+                }
+            }
+        }
+
+        pruned
+    }
+
+    fn subject(&self, container: usize) -> Option<String> {
+        let container = &self.containers[container];
+
+        let message = container
+            .message
+            .or_else(|| self.containers[*container.children.first()?].message)?;
+
+        Some(self.messages[message].subject_decoded())
+    }
+
+    fn is_dummy(&self, container: usize) -> bool {
+        self.containers[container].message.is_none()
+    }
+
+    // Merges root threads with the same base subject
+    fn group_by_subject(&mut self, roots: Vec<usize>) -> Vec<usize> {
+        let subjects = roots
+            .iter()
+            .map(|root| self.subject(*root).map(|s| base_subject(&s)))
+            .collect::<Vec<_>>();
+
+        // Per subject the root to merge into, preferring dummies and then non-replies
+        let mut table = HashMap::<&str, (usize, bool)>::new();
+
+        for (root, subject) in roots.iter().zip(&subjects) {
+            let Some((subject, reply)) = subject.as_ref().filter(|(s, _)| !s.is_empty()) else {
+                continue;
+            };
+
+            let replace = match table.get(subject.as_str()) {
+                Some((old, old_reply)) => match self.is_dummy(*old) {
+                    true => false,
+                    false => self.is_dummy(*root) || (*old_reply && !reply),
+                },
+                None => true,
+            };
+
+            if replace {
+                table.insert(subject, (*root, *reply));
+            }
+        }
+
+        let mut grouped = roots.clone();
+
+        for (root, subject) in roots.iter().zip(&subjects) {
+            let Some((subject, reply)) = subject.as_ref().filter(|(s, _)| !s.is_empty()) else {
+                continue;
+            };
+
+            let Some((other, other_reply)) = table.get(subject.as_str()).copied() else {
+                continue;
+            };
+
+            if other == *root {
+                continue;
+            }
+
+            grouped.retain(|r| r != root);
+
+            match (self.is_dummy(other), self.is_dummy(*root)) {
+                (true, true) => {
+                    let children = std::mem::take(&mut self.containers[*root].children);
+                    for child in children {
+                        self.link(other, child);
+                    }
+                }
+                (true, false) => self.link(other, *root),
+                _ if !other_reply && *reply => self.link(other, *root),
+                _ => {
+                    let dummy = self.create();
+                    self.link(dummy, other);
+                    self.link(dummy, *root);
+
+                    if let Some(position) = grouped.iter().position(|r| *r == other) {
+                        grouped[position] = dummy;
+                    }
+                    table.insert(subject, (dummy, false));
+                }
+            }
+        }
+
+        grouped
+    }
+
+    fn node(&self, container: usize) -> ThreadNode<'a> {
+        let container = &self.containers[container];
+
+        ThreadNode {
+            message: container.message.map(|m| &self.messages[m]),
+            children: container.children.iter().map(|c| self.node(*c)).collect(),
+        }
+    }
+}
+
+fn sort(nodes: &mut [ThreadNode]) {
+    nodes.iter_mut().for_each(|node| sort(&mut node.children));
+    nodes.sort_by_key(|node| node.date());
+}
+
+// Subject without reply and forward prefixes or list tags, and whether any prefix was removed
+fn base_subject(subject: &str) -> (String, bool) {
+    let mut subject = subject.trim();
+    let mut reply = false;
+
+    loop {
+        let lower = subject.to_ascii_lowercase();
+
+        let prefix = ["re", "fwd", "fw", "aw", "sv"].iter().find_map(|prefix| {
+            let rest = lower.strip_prefix(prefix)?;
+
+            // Counted prefixes like `Re[2]:` or `Re^2:`
+            let counter = match rest.as_bytes().first()? {
+                b'[' => rest.find(']')? + 1,
+                b'^' => 1 + rest[1..].bytes().take_while(u8::is_ascii_digit).count(),
+                _ => 0,
+            };
+
+            rest[counter..]
+                .starts_with(':')
+                .then_some(prefix.len() + counter + 1)
+        });
+
+        if let Some(length) = prefix {
+            subject = subject[length..].trim_start();
+            reply = true;
+        } else if subject.starts_with('[')
+            && let Some(end) = subject.find(']')
+            && end + 1 < subject.len()
+        {
+            subject = subject[end + 1..].trim_start();
+        } else {
+            break;
+        }
+    }
+
+    (subject.trim_end().to_lowercase(), reply)
+}
@@ -0,0 +1,100 @@
+use crate::connection::NewsConnection;
+use crate::messages::group::GroupRequest;
+use crate::messages::head::HeadType;
+use crate::messages::navigation::NextRequest;
+use crate::messages::stat::StatRequest;
+use crate::{Error, Result};
+use futures_util::stream::{self, Stream};
+
+#[derive(Debug, Clone)]
+pub struct ArticlePointer {
+    number: usize,
+    id: String,
+}
+
+// Walks a group article by article using STAT for the first and NEXT for every following
+// article, so that gaps in the numbering are skipped by the server. When the first article is
+// gone the walk continues with NEXT, it ends once the server has no next article. The walked
+// article is the current article of the connection, it can be fetched with `HeadType::Empty`.
+pub struct GroupWalker<'a> {
+    connection: &'a mut NewsConnection,
+    started: bool,
+    done: bool,
+}
+
+impl ArticlePointer {
+    fn new(number: usize, id: &str) -> Self {
+        Self {
+            number,
+            id: id.to_string(),
+        }
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<'a> GroupWalker<'a> {
+    pub async fn new(connection: &'a mut NewsConnection, group: impl ToString) -> Result<Self> {
+        let response = connection.request(GroupRequest::new(group)).await?;
+
+        if !response.ok() {
+            return Err(Error::UnexpectedResponseCode(response.code()));
+        }
+
+        Ok(Self {
+            connection,
+            started: false,
+            done: response.number() == 0,
+        })
+    }
+
+    pub fn connection(&mut self) -> &mut NewsConnection {
+        self.connection
+    }
+
+    // The walk as a stream, for when the walked articles are not fetched in between
+    pub fn into_stream(self) -> impl Stream<Item = Result<ArticlePointer>> + 'a {
+        stream::try_unfold(self, |mut walker| async move {
+            Ok(walker.next().await?.map(|pointer| (pointer, walker)))
+        })
+    }
+
+    pub async fn next(&mut self) -> Result<Option<ArticlePointer>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if !self.started {
+            self.started = true;
+
+            let response = self
+                .connection
+                .request(StatRequest::new(HeadType::Empty))
+                .await?;
+
+            // The low-water article may have expired or been cancelled
+            match response.code() {
+                223 => return Ok(Some(ArticlePointer::new(response.number(), response.id()))),
+                420 | 423 => {}
+                code => return Err(Error::UnexpectedResponseCode(code)),
+            }
+        }
+
+        let response = self.connection.request(NextRequest::new()).await?;
+
+        match response.code() {
+            223 => Ok(Some(ArticlePointer::new(response.number(), response.id()))),
+            421 => {
+                self.done = true;
+                Ok(None)
+            }
+            code => Err(Error::UnexpectedResponseCode(code)),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{Error, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// RFC 3977 wildmat: a comma separated list of patterns, each optionally negated with `!`.
+// Patterns support `*`, `?`, `[...]` and `\` escapes. The last pattern that matches decides.
+#[derive(Debug, Clone)]
+pub struct Wildmat {
+    source: String,
+    patterns: Vec<Pattern>,
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    negated: bool,
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Literal(char),
+    Any,
+    Star,
+    Class(bool, Vec<(char, char)>),
+}
+
+impl Wildmat {
+    pub fn new(wildmat: impl ToString) -> Result<Self> {
+        let source = wildmat.to_string();
+        let patterns = split(&source)
+            .into_iter()
+            .map(Pattern::parse)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { source, patterns })
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.chars().collect::<Vec<_>>();
+
+        self.patterns
+            .iter()
+            .rev()
+            .find(|p| matches(&p.tokens, &text))
+            .map(|p| !p.negated)
+            .unwrap_or(false)
+    }
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self> {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '*' => Token::Star,
+                '?' => Token::Any,
+                '\\' => Token::Literal(chars.next().ok_or(Error::InvalidWildmat)?),
+                '[' => parse_class(&mut chars)?,
+                c => Token::Literal(c),
+            };
+
+            tokens.push(token);
+        }
+
+        Ok(Self { negated, tokens })
+    }
+}
+
+// Splits at commas that are neither escaped nor part of a character class
+fn split(wildmat: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    let mut class = false;
+
+    for (i, c) in wildmat.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' if !class => class = true,
+            ']' if class => class = false,
+            ',' if !class => {
+                parts.push(&wildmat[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&wildmat[start..]);
+    parts
+}
+
+fn parse_class(chars: &mut std::str::Chars) -> Result<Token> {
+    let mut negated = false;
+    let mut ranges = Vec::new();
+    let mut first = true;
+
+    loop {
+        let c = chars.next().ok_or(Error::InvalidWildmat)?;
+
+        let c = match c {
+            '^' if first && !negated => {
+                negated = true;
+                continue;
+            }
+            // A leading `]` is part of the class
+            ']' if !first => break,
+            '\\' => chars.next().ok_or(Error::InvalidWildmat)?,
+            c => c,
+        };
+
+        first = false;
+
+        let mut lookahead = chars.clone();
+        if lookahead.next() == Some('-')
+            && let Some(end) = lookahead.next()
+            && end != ']'
+        {
+            *chars = lookahead;
+            ranges.push((c, end));
+        } else {
+            ranges.push((c, c));
+        }
+    }
+
+    Ok(Token::Class(negated, ranges))
+}
+
+fn matches(tokens: &[Token], text: &[char]) -> bool {
+    let (mut t, mut s) = (0, 0);
+    let mut backtrack = None;
+
+    while s < text.len() {
+        match tokens.get(t) {
+            Some(Token::Star) => {
+                backtrack = Some((t, s));
+                t += 1;
+                continue;
+            }
+            Some(Token::Any) => {
+                t += 1;
+                s += 1;
+                continue;
+            }
+            Some(Token::Literal(c)) if *c == text[s] => {
+                t += 1;
+                s += 1;
+                continue;
+            }
+            Some(Token::Class(negated, ranges))
+                if ranges
+                    .iter()
+                    .any(|(low, high)| (*low..=*high).contains(&text[s]))
+                    != *negated =>
+            {
+                t += 1;
+                s += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // Let the last star consume one more character
+        match backtrack {
+            Some((star, position)) => {
+                t = star + 1;
+                s = position + 1;
+                backtrack = Some((star, position + 1));
+            }
+            None => return false,
+        }
+    }
+
+    tokens[t..].iter().all(|token| matches!(token, Token::Star))
+}
+
+impl FromStr for Wildmat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Wildmat::new(s)
+    }
+}
+
+impl Display for Wildmat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}